@@ -1,60 +0,0 @@
-def get_list(text: str) -> list:
-    """ 括号匹配提取列表
-
-    Args:
-        text (str): 待提取字符串
-
-    Returns:
-        list: 列表
-    """
-    pass
-
-def structure(
-        detections: list[tuple[str, tuple[float, ...]]],
-        iou_threshold: float) -> list[tuple[str, tuple[float, ...]]]:
-    """ 检测结果后处理
-
-    Args:
-        detections (list[tuple[str, tuple[float, ...]]]): 检测结果
-        iou_threshold (float): iou 阈值
-
-    Returns:
-        list[tuple[str, tuple[float, ...]]]: 处理后检测结果
-    """
-    pass
-
-def get_longest_seq(
-        nums: list[int]) -> tuple[int, int]:
-    """ 找到一个最长的连续序列的起点和终点
-
-    Args:
-        nums (list[int]): 序列
-
-    Returns:
-        tuple[int, int]: 起点数字和终点数字
-    """
-    pass
-
-def optimize_length(strings: list[str], n: int) -> list[str]:
-    """ 将数组中的字符串到调整到目标长度附近
-
-    Args:
-        strings (list[str]): 字符串数组
-        n (int): 目标长度
-
-    Returns:
-        list[str]: 调整后的字符串数组
-    """
-    pass
-
-def merge(strings: list[str], n: int) -> list[str]:
-    """ 将字符串数组合并到目标长度附近
-
-    Args:
-        strings (list[str]): 字符串数组
-        n (int): 目标长度
-
-    Returns:
-        list[str]: 合并后的字符串数组
-    """
-    pass
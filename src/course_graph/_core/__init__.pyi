@@ -0,0 +1,1166 @@
+from typing import Any
+
+import numpy as np
+
+from . import chunk as chunk, kg as kg, latex as latex, layout as layout, parse as parse
+
+# 以下顶层名称为已弃用的别名, 访问时给出 DeprecationWarning, 请改用对应的子模块
+from .chunk import (
+    optimize_length as optimize_length,
+    merge as merge,
+)
+from .layout import (
+    structure as structure,
+)
+from .kg import (
+    dedup_triples as dedup_triples,
+    merge_triples as merge_triples,
+    normalize_relations as normalize_relations,
+    KnowledgeGraph as KnowledgeGraph,
+    to_cypher as to_cypher,
+    to_neo4j_csv as to_neo4j_csv,
+    to_rdf as to_rdf,
+    to_graphml as to_graphml,
+    to_jsonld as to_jsonld,
+    find_cycles as find_cycles,
+    learning_path as learning_path,
+    subgraph as subgraph,
+    components as components,
+    pagerank as pagerank,
+    degree_centrality as degree_centrality,
+    detect_communities as detect_communities,
+    shortest_path as shortest_path,
+    diff_graphs as diff_graphs,
+    merge_graphs as merge_graphs,
+    canonicalize_inverse as canonicalize_inverse,
+    validate_triples as validate_triples,
+    cooccurrence_graph as cooccurrence_graph,
+    GraphStats as GraphStats,
+    graph_stats as graph_stats,
+    build_alias_table as build_alias_table,
+    link_entities as link_entities,
+)
+from .parse import (
+    get_list as get_list,
+    parse_triples as parse_triples,
+    parse_enumerated as parse_enumerated,
+    parse_key_values as parse_key_values,
+    validate_extraction as validate_extraction,
+    isolate_structured as isolate_structured,
+    get_all_json as get_all_json,
+    get_yaml_from_string as get_yaml_from_string,
+    dedup_items as dedup_items,
+    parse_batch as parse_batch,
+)
+
+class CourseKgError(ValueError):
+    """ 扩展模块异常的基类
+    """
+
+class ChunkingError(CourseKgError):
+    """ 文本分块参数或输入不合法
+    """
+
+class LayoutError(CourseKgError):
+    """ 版面检测结果 (检测框) 不合法
+    """
+
+class ParseError(CourseKgError):
+    """ 解析失败, 用于区分 "没有可解析的内容" 与 "内容格式错误"; position 为出错位置的字符偏移 (未知时为 None), reason 为失败原因
+    """
+    position: int | None
+    reason: str
+
+class CoreConfig:
+    """ 各处理函数共用的默认参数, 传入 config 参数的函数在未显式指定对应参数时使用其中的值
+    """
+    chunk_size: int
+    overlap: int
+    delimiters: list[str]
+    iou_threshold: float
+    similarity_threshold: float
+    dedup_threshold: float
+    grapheme_safe: bool
+    stable: bool
+
+    def __init__(
+            self,
+            chunk_size: int = 400,
+            overlap: int = 0,
+            delimiters: list[str] | None = None,
+            iou_threshold: float = 0.1,
+            similarity_threshold: float = 0.85,
+            dedup_threshold: float = 0.8,
+            grapheme_safe: bool = True,
+            stable: bool = False) -> None:
+        """ 创建配置
+
+        Args:
+            chunk_size (int, optional): optimize_length 与 merge 的目标长度. Defaults to 400.
+            overlap (int, optional): merge 结果中相邻块重叠的字符数. Defaults to 0.
+            delimiters (list[str] | None, optional): 切分句子的分隔符, 为 None 时为 ['。']. Defaults to None.
+            iou_threshold (float, optional): structure 的 iou 阈值. Defaults to 0.1.
+            similarity_threshold (float, optional): merge_triples, build_alias_table, link_entities 与 dedup_items 的相似度阈值. Defaults to 0.85.
+            dedup_threshold (float, optional): MinHasher.dedup 的 Jaccard 相似度阈值. Defaults to 0.8.
+            grapheme_safe (bool, optional): optimize_length 与 merge 的切分点与 overlap 是否对齐到字素簇边界, 避免拆开 emoji 与组合字符, 关闭时略快. Defaults to True.
+            stable (bool, optional): structure 是否使用确定性的去重规则, 开启后相同输入总是得到相同输出, 便于回归测试. Defaults to False.
+
+        Raises:
+            ChunkingError: chunk_size 不大于 0, overlap 不小于 chunk_size, 或分隔符为空
+            LayoutError: iou_threshold 不在 [0, 1] 内
+            ValueError: 相似度阈值不在 [0, 1] 内
+        """
+        pass
+
+def get_longest_seq(
+        nums: list[int]) -> tuple[int, int]:
+    """ 找到一个最长的连续序列的起点和终点
+
+    Args:
+        nums (list[int]): 序列
+
+    Returns:
+        tuple[int, int]: 起点数字和终点数字
+    """
+    pass
+
+def markdown_to_text(md: str) -> str:
+    """ 将 Markdown 转换为纯文本, 去除强调标记、链接 (保留锚文本)、图片、代码块与 HTML 标签
+
+    Args:
+        md (str): Markdown 文本
+
+    Returns:
+        str: 纯文本
+    """
+    pass
+
+def split_markdown_sections(md: str, max_level: int) -> list[tuple[list[str], str]]:
+    """ 按标题将 Markdown 文档切分为章节, 超过 max_level 的标题保留在正文中
+
+    Args:
+        md (str): Markdown 文本
+        max_level (int): 参与切分的最大标题级别
+
+    Returns:
+        list[tuple[list[str], str]]: (祖先标题路径, 正文) 列表
+    """
+    pass
+
+def split_markdown_sections_file(path: str, max_level: int) -> list[tuple[list[str], str]]:
+    """ 以内存映射方式读取 Markdown 文件并按标题切分为章节, 结果同 split_markdown_sections
+
+    Args:
+        path (str): UTF-8 Markdown 文件路径
+        max_level (int): 参与切分的最大标题级别
+
+    Raises:
+        ValueError: 文件无法读取或不是合法的 UTF-8
+
+    Returns:
+        list[tuple[list[str], str]]: (祖先标题路径, 正文) 列表
+    """
+    pass
+
+def get_markdown_code_blocks(md: str) -> list[tuple[str, str, int]]:
+    """ 提取 Markdown 中的围栏代码块
+
+    Args:
+        md (str): Markdown 文本
+
+    Returns:
+        list[tuple[str, str, int]]: (语言, 代码, 代码块起始字符偏移) 列表, 未标注语言时为空字符串
+    """
+    pass
+
+def parse_markdown_lists(md: str) -> list[tuple[str, int, list[str]]]:
+    """ 提取 Markdown 中的 (有序/无序) 列表项及其嵌套关系
+
+    Args:
+        md (str): Markdown 文本
+
+    Returns:
+        list[tuple[str, int, list[str]]]: (条目文本, 嵌套深度, 祖先条目文本) 列表, 顶层深度为 0
+    """
+    pass
+
+def html_to_text(html: str) -> str:
+    """ 提取 HTML 中的正文文本, 忽略脚本与样式, 块级元素之间换行
+
+    Args:
+        html (str): HTML 文本
+
+    Returns:
+        str: 纯文本
+    """
+    pass
+
+def get_html_outline(html: str) -> list[tuple[int, str]]:
+    """ 提取 HTML 中 h1-h6 标题构成的大纲
+
+    Args:
+        html (str): HTML 文本
+
+    Returns:
+        list[tuple[int, str]]: (标题级别, 标题文本) 列表
+    """
+    pass
+
+def parse_cn_heading(line: str) -> tuple[int, int, str] | None:
+    """ 解析中文标题前缀, 支持 第X篇/第X章/第X节/一、/（一）/（1）
+
+    级别依次为: 篇/编/部分 0, 章/讲/课 1, 节 2, 一、 3, （一） 4, （1） 5
+
+    Args:
+        line (str): 标题行
+
+    Returns:
+        tuple[int, int, str] | None: (级别, 编号, 标题), 不是标题时返回 None
+    """
+    pass
+
+def parse_cn_headings_file(path: str) -> list[tuple[int, int, int, str]]:
+    """ 以内存映射方式读取文本文件, 逐行调用 parse_cn_heading 提取中文标题大纲
+
+    Args:
+        path (str): UTF-8 文本文件路径
+
+    Raises:
+        ValueError: 文件无法读取或不是合法的 UTF-8
+
+    Returns:
+        list[tuple[int, int, int, str]]: (行号, 级别, 编号, 标题) 列表, 行号从 0 开始, 级别规则同 parse_cn_heading
+    """
+    pass
+
+def cn_num_to_int(s: str) -> int:
+    """ 中文数字转整数, 支持 零/两/十百千万亿 及 "二〇二四" 式逐位写法
+
+    Args:
+        s (str): 中文数字
+
+    Raises:
+        ValueError: 无法解析
+
+    Returns:
+        int: 整数
+    """
+    pass
+
+def int_to_cn_num(n: int) -> str:
+    """ 整数转中文数字
+
+    Args:
+        n (int): 非负整数, 小于 10^16
+
+    Raises:
+        ValueError: 数字过大
+
+    Returns:
+        str: 中文数字
+    """
+    pass
+
+def parse_toc(text_lines: list[str]) -> list[tuple[str, int, int]]:
+    """ 解析目录页, 识别形如 "第3章 线性表 ........ 45" 的目录行
+
+    Args:
+        text_lines (list[str]): 目录页文本行
+
+    Returns:
+        list[tuple[str, int, int]]: (标题, 级别, 页码) 列表, 级别规则同 parse_cn_heading, "3.2.1" 式编号取段数
+    """
+    pass
+
+def parse_numbering(line: str) -> tuple[list[int], str] | None:
+    """ 解析 "3.2.1 栈的应用" 式的标题编号
+
+    Args:
+        line (str): 标题行
+
+    Returns:
+        tuple[list[int], str] | None: (编号路径, 标题), 没有编号时返回 None
+    """
+    pass
+
+def build_heading_tree(
+        headings: list[str]) -> tuple[list[tuple[list[int], str, int | None]], list[tuple[int, str]]]:
+    """ 由带编号的标题列表构建标题树, 并检查跳级、编号不连续等问题
+
+    Args:
+        headings (list[str]): 按顺序排列的标题
+
+    Returns:
+        tuple[list[tuple[list[int], str, int | None]], list[tuple[int, str]]]:
+            节点列表 (编号路径, 标题, 父节点在节点列表中的下标) 与问题列表 (输入标题下标, 描述), 无法解析编号的标题不进入节点列表
+    """
+    pass
+
+def match_sections(outline_a: list[str], outline_b: list[str], min_similarity: float = 0.6) -> list[tuple[int, int, float]]:
+    """ 按标题相似度与先后顺序对齐两个版本教材的章节大纲, 用于将人工审核过的图谱修改迁移到新版本
+
+    比较前去掉 "第X章"、"一、"、"3.2.1" 等编号, 并忽略空白、全角/半角与大小写差异;
+    在保持两侧顺序的前提下使配对标题的相似度之和最大
+
+    Args:
+        outline_a (list[str]): 旧版本的标题列表, 按出现顺序
+        outline_b (list[str]): 新版本的标题列表, 按出现顺序
+        min_similarity (float, optional): 允许配对的最小标题相似度 (编辑距离归一化). Defaults to 0.6.
+
+    Returns:
+        list[tuple[int, int, float]]: (outline_a 下标, outline_b 下标, 相似度) 列表, 两侧下标均递增, 未配对的标题不出现
+    """
+    pass
+
+def join_pdf_lines(lines: list[str]) -> list[str]:
+    """ 合并 PDF 提取文本中的硬换行, 去除英文断词连字符, 中文字符之间不插入空格
+
+    Args:
+        lines (list[str]): 文本行
+
+    Returns:
+        list[str]: 段落列表
+    """
+    pass
+
+def normalize_cjk_spacing(text: str) -> str:
+    """ 删除中文字符之间多余的空白 (如 OCR 产生的 "数 据 结 构"), 保留英文单词与数字两侧的空格
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        str: 处理后文本
+    """
+    pass
+
+def normalize_punct(text: str, mode: str = 'zh') -> str:
+    """ 全角/半角与标点规范化, 并折叠重复标点
+
+    Args:
+        text (str): 文本
+        mode (str, optional): zh: 全角字母数字转半角, 中文语境中的英文标点、括号、引号统一为中文形式, 连续 3 个及以上的 "." 转为 "……";
+            half: 全部转为半角英文标点. Defaults to 'zh'.
+
+    Raises:
+        ValueError: 未知的 mode
+
+    Returns:
+        str: 处理后文本
+    """
+    pass
+
+def t2s(text: str) -> str:
+    """ 繁体转简体 (逐字映射)
+
+    Args:
+        text (str): 繁体文本
+
+    Returns:
+        str: 简体文本
+    """
+    pass
+
+def s2t(text: str) -> str:
+    """ 简体转繁体 (逐字映射, 一简对多繁时取最常用的繁体字)
+
+    Args:
+        text (str): 简体文本
+
+    Returns:
+        str: 繁体文本
+    """
+    pass
+
+def clean_text(text: str,
+               nfkc: bool = True,
+               remove_control: bool = True,
+               remove_zero_width: bool = True,
+               remove_soft_hyphen: bool = True) -> str:
+    """ 一次遍历完成 NFKC 规范化与不可见字符清理
+
+    注意 NFKC 会将全角标点 (如 "，") 转为半角
+
+    Args:
+        text (str): 文本
+        nfkc (bool, optional): 是否进行 NFKC 规范化. Defaults to True.
+        remove_control (bool, optional): 是否删除控制字符 (保留换行与制表符). Defaults to True.
+        remove_zero_width (bool, optional): 是否删除零宽字符、方向控制符与 BOM. Defaults to True.
+        remove_soft_hyphen (bool, optional): 是否删除软连字符. Defaults to True.
+
+    Returns:
+        str: 清理后文本
+    """
+    pass
+
+def strip_page_furniture(pages: list[str],
+                         min_ratio: float = 0.5,
+                         window: int = 3) -> tuple[list[str], list[tuple[int, str]]]:
+    """ 删除页眉、页脚与页码: 在多页同一位置重复出现 (忽略数字差异) 的首尾行视为页眉页脚
+
+    首尾行中单独的阿拉伯数字与罗马数字 (全部大写或全部小写) 页码同样删除
+
+    Args:
+        pages (list[str]): 每页文本
+        min_ratio (float, optional): 判定为重复所需的最小页数占比. Defaults to 0.5.
+        window (int, optional): 每页首尾参与检测的非空行数. Defaults to 3.
+
+    Returns:
+        tuple[list[str], list[tuple[int, str]]]: 清理后的每页文本, 被删除的 (页下标, 行) 列表
+    """
+    pass
+
+def garbled_score(text: str) -> float:
+    """ 乱码程度评分: 替换字符、私用区字符及常见中文/英文/标点范围以外字符占非空白字符的比例
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        float: 0 到 1 之间的分数, 越高越可能是乱码
+    """
+    pass
+
+class TextStats:
+    """ 文本质量统计, 比例均以非空白字符数为分母
+    """
+    char_count: int
+    cjk_ratio: float
+    digit_ratio: float
+    punct_ratio: float
+    sentence_count: int
+    avg_sentence_len: float
+
+def text_stats(text: str) -> TextStats:
+    """ 统计字符数、中文/数字/标点占比、句子数与平均句长, 用于过滤封面、答案页与参考文献页
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        TextStats: 统计结果
+    """
+    pass
+
+def detect_language(text: str) -> str:
+    """ 判断文档的主要语言, 用于选择中文或英文的提示词模板与切分方式
+
+    汉字按字、英文按词计数比较占比, 再以高频虚词区分正文语言与夹杂的术语, 如夹杂大量英文术语的中文教材仍判为 'zh'
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        str: 'zh', 'en', 'mixed' (中英文正文均占相当比例), 或 'unknown' (没有汉字与英文单词, 或拉丁字母文本中没有任何英文虚词, 如代码)
+    """
+    pass
+
+def difficulty_score(text: str) -> float:
+    """ 估计文本难度, 用于为知识点标注难度等级
+
+    由平均句长 (10 到 60 字映射到 0 到 1, 权重 0.4)、生僻字占汉字的比例 (常用 3500 字以外, 0 到 10%, 权重 0.3)
+    与公式符号占可见字符的比例 (运算符、希腊字母、上下标、箭头等, 0 到 15%, 权重 0.3) 加权得到
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        float: 0 到 1 之间的得分, 越大越难, 空文本为 0.0
+    """
+    pass
+
+def count_tokens(text: str, model: str = 'qwen') -> int:
+    """ 估算文本在指定模型分词器下的 token 数, 用于分块预算, 无需在 Python 端加载分词器
+
+    按汉字、英文单词、数字、标点与换行分别以经验比例估算, 与真实分词结果可能相差约一成
+
+    Args:
+        text (str): 文本
+        model (str, optional): 模型名, 按前缀匹配 (不区分大小写): 'cl100k' (gpt-4, gpt-3.5, llama3), 'o200k' (gpt-4o, o1), 'qwen', 'glm', 'deepseek', 'llama' (Llama 2). Defaults to 'qwen'.
+
+    Raises:
+        ValueError: 未知的模型
+
+    Returns:
+        int: 估算的 token 数
+    """
+    pass
+
+def count_tokens_batch(texts: list[str], model: str = 'qwen') -> list[int]:
+    """ 并行估算一批文本的 token 数, 结果与逐个调用 count_tokens 一致
+
+    Args:
+        texts (list[str]): 文本列表
+        model (str, optional): 模型名, 同 count_tokens. Defaults to 'qwen'.
+
+    Raises:
+        ValueError: 未知的模型
+
+    Returns:
+        list[int]: 与输入一一对应的 token 数
+    """
+    pass
+
+def pack_context(chunks: list[str],
+                 scores: list[float],
+                 budget_tokens: int,
+                 template_overhead: int = 0,
+                 separator: str = '\n\n',
+                 model: str = 'qwen') -> tuple[str, list[int]]:
+    """ 在 token 预算内按得分从高到低贪心选取文本块并拼接为提示词上下文, 放不下的块跳过并继续尝试后续较短的块
+
+    token 数由 count_tokens 估算
+
+    Args:
+        chunks (list[str]): 候选文本块
+        scores (list[float]): 与 chunks 一一对应的相关性得分, 同分时下标小的优先
+        budget_tokens (int): 总 token 预算
+        template_overhead (int, optional): 提示词模板自身占用的 token 数, 从预算中扣除. Defaults to 0.
+        separator (str, optional): 块之间的分隔符, 其 token 数计入预算. Defaults to '\n\n'.
+        model (str, optional): 估算 token 数使用的模型, 同 count_tokens. Defaults to 'qwen'.
+
+    Raises:
+        ValueError: chunks 与 scores 长度不一致, 或未知的模型
+
+    Returns:
+        tuple[str, list[int]]: 拼接后的上下文, 以及选中块的下标 (按得分降序, 与拼接顺序一致)
+    """
+    pass
+
+def sentence_spans(text: str, delimiters: list[str] | None = None, unit: str = 'char') -> list[tuple[int, int]]:
+    """ 切分句子并返回各句在原文中的区间, 可直接用于切片原文
+
+    连续的句末标点及其后的闭合引号、括号归入同一句; 引号内的句末标点不切分, 引号以句末标点结束时在闭合引号之后切分.
+    区间不含句子首尾的空白, 仅由空白构成的片段不出现在结果中
+
+    Args:
+        text (str): 文本
+        delimiters (list[str] | None, optional): 句末分隔符, 为 None 时使用 。！？!?；… 以及后跟空白或闭合符号的 '.'. Defaults to None.
+        unit (str, optional): 偏移单位, 'char' 为 Python 字符串下标, 'byte' 为 UTF-8 字节偏移. Defaults to 'char'.
+
+    Raises:
+        ValueError: 未知的 unit
+
+    Returns:
+        list[tuple[int, int]]: (起始偏移, 结束偏移) 列表, 按出现顺序排列
+    """
+    pass
+
+def context_windows(sentences: list[tuple[int, int]],
+                    center_indices: list[int],
+                    before: int = 1,
+                    after: int = 1) -> list[tuple[int, int, list[int]]]:
+    """ 以包含实体的句子为中心取前后若干句作为上下文窗口, 用于展示证据, 重叠或相邻的窗口合并为一个
+
+    Args:
+        sentences (list[tuple[int, int]]): 句子区间, 通常为 sentence_spans 的结果
+        center_indices (list[int]): 中心句下标, 重复的下标只计一次
+        before (int, optional): 中心句之前的句数. Defaults to 1.
+        after (int, optional): 中心句之后的句数. Defaults to 1.
+
+    Raises:
+        ValueError: 中心句下标超出范围
+
+    Returns:
+        list[tuple[int, int, list[int]]]: (起始偏移, 结束偏移, 窗口内的中心句下标) 列表, 按位置排列, 偏移单位与 sentences 一致
+    """
+    pass
+
+def char_len_batch(texts: list[str]) -> list[int]:
+    """ 并行统计一批文本的字符数, 与逐个调用 len() 一致
+
+    Args:
+        texts (list[str]): 文本列表
+
+    Returns:
+        list[int]: 与输入一一对应的字符数
+    """
+    pass
+
+def handle_footnotes(text: str, mode: str = 'strip') -> tuple[str, list[tuple[int, str]]]:
+    """ 识别页面底部的脚注块与正文中的脚注标记 (①、[1]、¹), 删除或内联脚注
+
+    脚注块须以分隔线 (如 ——、____) 开头, 或其中的脚注在正文中有对应标记, 否则视为普通列举, 原样返回
+
+    Args:
+        text (str): 单页文本
+        mode (str, optional): strip: 删除脚注块与对应标记; inline: 将脚注内容以括号形式插入标记处. Defaults to 'strip'.
+
+    Raises:
+        ValueError: 未知的 mode
+
+    Returns:
+        tuple[str, list[tuple[int, str]]]: 处理后文本, (脚注编号, 脚注内容) 列表
+    """
+    pass
+
+def detect_references_section(
+        text: str) -> tuple[tuple[int, int] | None, list[tuple[int, int, str]]]:
+    """ 定位参考文献章节与编号的文献条目, 以便在概念抽取前排除
+
+    章节外的编号行仅在带有文献类型标识 ([M]、[J] 等) 或出版信息时才视为文献条目
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        tuple[tuple[int, int] | None, list[tuple[int, int, str]]]: 参考文献章节的字符区间 (未找到时为 None),
+            文献条目列表 (起始字符偏移, 结束字符偏移, 条目文本)
+    """
+    pass
+
+def extract_exercises(text: str) -> list[tuple[str, str, str, int, int]]:
+    """ 提取例题 (例1.2) 与习题、思考题章节中的编号题目
+
+    Args:
+        text (str): 教材文本
+
+    Returns:
+        list[tuple[str, str, str, int, int]]: (类别, 编号, 题目文本, 起始字符偏移, 结束字符偏移) 列表,
+            类别为 "例题" 或所在章节名 (习题、思考题等)
+    """
+    pass
+
+def extract_definitions(text: str) -> list[tuple[str, str]]:
+    """ 基于规则模板 (X是指…、所谓X，…、X，也称Y，…、把…称为X、X是一种…) 抽取定义句
+
+    Args:
+        text (str): 文本
+
+    Returns:
+        list[tuple[str, str]]: (术语, 定义句) 候选列表, "也称" 句式中的别名同样作为术语返回
+    """
+    pass
+
+class TermMatcher:
+    """ 基于 Aho-Corasick 自动机的术语匹配器, 重叠时取最长匹配
+    """
+
+    def __init__(self, terms: list[str], ignore_case: bool = False) -> None:
+        """ 由术语表构建匹配器
+
+        Args:
+            terms (list[str]): 术语表
+            ignore_case (bool, optional): 是否忽略 ASCII 大小写. Defaults to False.
+        """
+        pass
+
+    def find_all(self, text: str) -> list[tuple[str, int, int]]:
+        """ 查找文本中出现的全部术语
+
+        Args:
+            text (str): 文本
+
+        Returns:
+            list[tuple[str, int, int]]: (术语, 起始字符偏移, 结束字符偏移) 列表
+        """
+        pass
+
+    def __len__(self) -> int:
+        pass
+
+def locate_mentions(text: str, entities: list[str]) -> list[list[tuple[int, int]]]:
+    """ 定位实体在原文中的字符区间, 匹配时忽略空白、标点、全半角与大小写差异
+
+    Args:
+        text (str): 原文
+        entities (list[str]): 实体字符串
+
+    Returns:
+        list[list[tuple[int, int]]]: 与 entities 一一对应的 (起始字符偏移, 结束字符偏移) 列表, 未找到时为空列表
+    """
+    pass
+
+def scan_patterns(text: str, patterns: dict[str, str]) -> dict[str, list[tuple[int, int, str]]]:
+    """ 使用 RegexSet 一次编译、一次扫描多个命名正则, 按名称分组返回匹配
+
+    Args:
+        text (str): 文本
+        patterns (dict[str, str]): 名称到正则表达式的映射 (Rust regex 语法)
+
+    Raises:
+        ValueError: 正则无法编译
+
+    Returns:
+        dict[str, list[tuple[int, int, str]]]: 名称到 (起始字符偏移, 结束字符偏移, 匹配文本) 列表的映射, 按名称排序, 未命中的名称不出现
+    """
+    pass
+
+def warm_up() -> None:
+    """ 预先编译各抽取函数使用的正则表达式与查找表, 并加载分词词典
+
+    正则在首次使用时才会编译, 可在服务启动时调用本函数以避免首次请求的延迟
+    """
+    pass
+
+def edit_distance(a: str, b: str, max_distance: int | None = None) -> int:
+    """ 计算两个字符串的编辑距离 (Levenshtein 距离), 按字符计
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+        max_distance (int | None, optional): 距离上限, 超过后提前结束计算. Defaults to None.
+
+    Returns:
+        int: 编辑距离, 超过 max_distance 时返回 max_distance + 1
+    """
+    pass
+
+def similarity(a: str, b: str, min_similarity: float = 0.0) -> float:
+    """ 计算基于编辑距离的归一化相似度, 即 1 - 编辑距离 / 较长字符串长度
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+        min_similarity (float, optional): 相似度下限, 确定低于该值后提前结束计算. Defaults to 0.0.
+
+    Returns:
+        float: 0 到 1 之间的相似度, 低于 min_similarity 时返回 0.0, 两者均为空串时返回 1.0
+    """
+    pass
+
+def similarity_matrix(strings: list[str], metric: str = 'levenshtein', n: int = 2) -> list[float]:
+    """ 并行计算字符串两两之间的相似度矩阵, 可用于跨章节的同义概念聚类
+
+    Args:
+        strings (list[str]): 字符串列表
+        metric (str, optional): 相似度度量, 'levenshtein' 为归一化编辑距离相似度, 'jaccard' 为字符 n-gram 的 Jaccard 系数. Defaults to 'levenshtein'.
+        n (int, optional): jaccard 度量使用的 n-gram 长度. Defaults to 2.
+
+    Raises:
+        ValueError: 未知的度量或 n 为 0
+
+    Returns:
+        list[float]: 按行展开的 len(strings) * len(strings) 对称矩阵, 对角线为 1.0
+    """
+    pass
+
+class MinHasher:
+    """ 基于 MinHash 与 LSH 分桶的近重复文本块检测器
+    """
+
+    def __init__(self, shingle_size: int = 5, num_perm: int = 128, bands: int = 16, seed: int = 1) -> None:
+        """ 创建检测器
+
+        Args:
+            shingle_size (int, optional): 字符 shingle 长度, 计算前会去除空白. Defaults to 5.
+            num_perm (int, optional): 置换 (哈希函数) 数量. Defaults to 128.
+            bands (int, optional): LSH 条带数, 每条带的行数为 num_perm / bands. Defaults to 16.
+            seed (int, optional): 置换参数的随机种子. Defaults to 1.
+
+        Raises:
+            ValueError: 参数为 0 或 num_perm 不能被 bands 整除
+        """
+        pass
+
+    def signature(self, text: str) -> list[int]:
+        """ 计算文本的 MinHash 签名
+
+        Args:
+            text (str): 文本
+
+        Returns:
+            list[int]: 长度为 num_perm 的签名
+        """
+        pass
+
+    def dedup(self, chunks: list[str], threshold: float | None = None, config: CoreConfig | None = None) -> list[list[int]]:
+        """ 对文本块分组, 找出近重复的块
+
+        Args:
+            chunks (list[str]): 文本块列表
+            threshold (float | None, optional): 估计 Jaccard 相似度阈值, 为 None 时取 config.dedup_threshold (默认 0.8). Defaults to None.
+            config (CoreConfig | None, optional): 默认参数. Defaults to None.
+
+        Returns:
+            list[list[int]]: 近重复块下标的分组, 仅包含两个及以上成员的组
+        """
+        pass
+
+def simhash(text: str, shingle_size: int = 3) -> int:
+    """ 计算文本的 64 位 SimHash 指纹, 以去除空白后的字符 shingle 为特征, 结果跨进程稳定
+
+    Args:
+        text (str): 文本
+        shingle_size (int, optional): 字符 shingle 长度. Defaults to 3.
+
+    Raises:
+        ValueError: shingle_size 为 0
+
+    Returns:
+        int: 64 位无符号指纹
+    """
+    pass
+
+def hamming_distance(a: int, b: int) -> int:
+    """ 计算两个 64 位指纹的汉明距离
+
+    Args:
+        a (int): 指纹 a
+        b (int): 指纹 b
+
+    Returns:
+        int: 不同的二进制位数
+    """
+    pass
+
+def ngram_jaccard(a: str, b: str, n: int = 2) -> float:
+    """ 计算两个字符串字符 n-gram 集合的 Jaccard 系数, 适用于不分词的中文别名比较
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+        n (int, optional): n-gram 长度, 短于 n 的字符串整体作为一个 gram. Defaults to 2.
+
+    Raises:
+        ValueError: n 为 0
+
+    Returns:
+        float: 0 到 1 之间的系数, 两者均为空串时返回 1.0
+    """
+    pass
+
+def lcs(a: str, b: str) -> tuple[int, list[tuple[int, int, int, int]]]:
+    """ 计算两个字符串的最长公共子序列, 可用于 OCR 文本与规范化引文的对齐
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+
+    Returns:
+        tuple[int, list[tuple[int, int, int, int]]]: 公共子序列长度, 以及匹配部分合并成的 (a 起始, a 结束, b 起始, b 结束) 字符区间列表
+    """
+    pass
+
+def longest_common_substring(a: str, b: str) -> tuple[int, list[tuple[int, int, int, int]]]:
+    """ 计算两个字符串的最长公共子串, 存在多个时取在 a 中最先结束的一个
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+
+    Returns:
+        tuple[int, list[tuple[int, int, int, int]]]: 公共子串长度, 以及 (a 起始, a 结束, b 起始, b 结束) 字符区间列表, 无公共子串时为空
+    """
+    pass
+
+def align_evidence(chunk: str, claim: str) -> tuple[int, int, float]:
+    """ 在文本块中寻找与大模型改写后的陈述最匹配的片段 (字符级局部比对), 作为三元组的可核验证据
+
+    比较时忽略全角/半角与英文大小写差异
+
+    Args:
+        chunk (str): 文本块
+        claim (str): 陈述
+
+    Returns:
+        tuple[int, int, float]: (起始字符偏移, 结束字符偏移, 得分), 得分为比对得分与陈述完全匹配时得分之比, 在 0 到 1 之间; 没有任何相同字符时为 (0, 0, 0.0)
+    """
+    pass
+
+def fuzzy_match(query: str, candidates: list[str], k: int = 5, min_score: float = 0.0) -> list[tuple[int, float]]:
+    """ 在候选词表中查找与查询最相似的 k 个字符串, 得分为归一化编辑距离相似度, 计算时按长度差与当前第 k 名得分剪枝
+
+    Args:
+        query (str): 查询字符串
+        candidates (list[str]): 候选字符串列表
+        k (int, optional): 返回数量. Defaults to 5.
+        min_score (float, optional): 最低得分. Defaults to 0.0.
+
+    Returns:
+        list[tuple[int, float]]: (候选下标, 得分) 列表, 按得分降序, 同分时下标小者在前
+    """
+    pass
+
+def topk_cosine(query: np.ndarray, matrix: np.ndarray, k: int = 10) -> list[tuple[int, float]]:
+    """ 在向量矩阵中按余弦相似度检索与查询最相近的 k 行, 各行并行计算
+
+    Args:
+        query (np.ndarray): float32 一维查询向量
+        matrix (np.ndarray): float32 二维矩阵, 每行为一个向量
+        k (int, optional): 返回数量. Defaults to 10.
+
+    Raises:
+        ValueError: 查询向量维度与矩阵列数不一致
+
+    Returns:
+        list[tuple[int, float]]: (行下标, 余弦相似度) 列表, 按相似度降序, 零向量的相似度记为 0
+    """
+    pass
+
+class Bm25Index:
+    """ BM25 词法检索索引, 中文按字符二元组切分, 拉丁字母与数字按词切分
+    """
+
+    def __init__(self, k1: float = 1.5, b: float = 0.75) -> None:
+        """ 创建空索引
+
+        Args:
+            k1 (float, optional): 词频饱和参数. Defaults to 1.5.
+            b (float, optional): 文档长度归一化参数. Defaults to 0.75.
+
+        Raises:
+            ValueError: k1 为负或 b 不在 [0, 1] 内
+        """
+        pass
+
+    def add(self, docs: list[str]) -> None:
+        """ 追加文档, 文档下标按加入顺序递增
+
+        Args:
+            docs (list[str]): 文档列表
+        """
+        pass
+
+    def search(self, query: str, k: int = 10) -> list[tuple[int, float]]:
+        """ 检索与查询最相关的文档
+
+        Args:
+            query (str): 查询文本
+            k (int, optional): 返回数量. Defaults to 10.
+
+        Returns:
+            list[tuple[int, float]]: (文档下标, BM25 得分) 列表, 按得分降序, 仅包含命中查询词项的文档
+        """
+        pass
+
+    def __len__(self) -> int:
+        pass
+
+def extract_keywords(docs: list[str], top_k: int = 10, tokenizer: str = 'bigram') -> list[list[tuple[str, float]]]:
+    """ 基于 TF-IDF 为每篇文档抽取关键词, 忽略单字、纯数字、标点与内置停用词
+
+    Args:
+        docs (list[str]): 文档列表, 如各章节文本
+        top_k (int, optional): 每篇文档返回的关键词数量. Defaults to 10.
+        tokenizer (str, optional): 切分方式, 'bigram' 为中文字符二元组加拉丁字母与数字按词, 'jieba' 为 jieba 精确模式分词. Defaults to 'bigram'.
+
+    Raises:
+        ValueError: 未知的切分方式
+
+    Returns:
+        list[list[tuple[str, float]]]: 与 docs 对应的 (关键词, 权重) 列表, 按权重降序
+    """
+    pass
+
+def segment(text: str, mode: str = 'default', hmm: bool = True) -> list[str]:
+    """ 使用 jieba 对中文文本分词
+
+    Args:
+        text (str): 文本
+        mode (str, optional): 分词模式, 'default' 为精确模式, 'all' 为全模式, 'search' 为搜索引擎模式. Defaults to 'default'.
+        hmm (bool, optional): 是否使用 HMM 识别未登录词, 全模式下无效. Defaults to True.
+
+    Raises:
+        ValueError: 未知的分词模式
+
+    Returns:
+        list[str]: 词列表
+    """
+    pass
+
+def load_user_dict(path: str) -> None:
+    """ 加载自定义词典, 格式与 jieba 相同, 每行为 "词 [词频] [词性]", 对之后的全部分词调用生效
+
+    Args:
+        path (str): 词典文件路径
+
+    Raises:
+        ValueError: 文件无法打开或格式错误
+    """
+    pass
+
+def add_words(words: list[str], freq: int | None = None) -> None:
+    """ 向分词词典添加词语, 如课程术语表
+
+    Args:
+        words (list[str]): 词语列表
+        freq (int | None, optional): 词频, 为 None 时自动计算能使该词被切出的词频. Defaults to None.
+    """
+    pass
+
+def filter_stopwords(tokens: list[str], extra_stopwords: list[str] | None = None) -> list[str]:
+    """ 过滤停用词, 内置常用中英文停用词, 同时去除仅由空白与标点构成的词, 英文不区分大小写
+
+    Args:
+        tokens (list[str]): 词列表
+        extra_stopwords (list[str] | None, optional): 额外的领域停用词. Defaults to None.
+
+    Returns:
+        list[str]: 过滤后的词列表, 保持原有顺序
+    """
+    pass
+
+def to_pinyin(text: str, style: str = 'plain') -> list[str]:
+    """ 逐字转换为拼音, 多音字取最常用读音
+
+    Args:
+        text (str): 文本
+        style (str, optional): 拼音风格, 'plain' 为不带声调, 'tone' 为带声调符号, 'tone_num' 为数字声调, 'first_letter' 为首字母. Defaults to 'plain'.
+
+    Raises:
+        ValueError: 未知的拼音风格
+
+    Returns:
+        list[str]: 与输入字符一一对应的列表, 非汉字原样保留
+    """
+    pass
+
+def pinyin_similarity(a: str, b: str, min_similarity: float = 0.0) -> float:
+    """ 计算按读音比较的归一化编辑距离相似度, 同音字 (含多音字的任一读音, 不计声调) 视为相同, 可发现 OCR 造成的同音误识
+
+    Args:
+        a (str): 字符串 a
+        b (str): 字符串 b
+        min_similarity (float, optional): 相似度下限, 确定低于该值后提前结束计算. Defaults to 0.0.
+
+    Returns:
+        float: 0 到 1 之间的相似度, 计算时忽略空白, 非汉字不区分全半角与 ASCII 大小写
+    """
+    pass
+
+def check_stubs(stub_dir: str) -> list[str]:
+    """ 检查 stub_dir 下手写的类型存根 (__init__.pyi 与各子模块的 .pyi) 是否与运行时的导出一致
+
+    报告存根缺少或多出的函数、类、方法与属性, 参数名不一致, 以及可按字面量比较的默认值不一致; 不检查类型标注本身.
+    可在测试中对 src/course_graph/_core 调用, 返回空列表表示存根已同步
+
+    Args:
+        stub_dir (str): 存根所在目录
+
+    Raises:
+        ValueError: 存根文件无法读取
+
+    Returns:
+        list[str]: 不一致之处, 形如 'kg.pyi: to_cypher 的参数不一致: ...'
+    """
+    pass
+
+def set_num_threads(n: int) -> None:
+    """ 设置并行批处理函数 (如 similarity_matrix, MinHasher.dedup, merge_triples, link_entities, parse_batch) 使用的线程数
+
+    Args:
+        n (int): 线程数, 为 0 时恢复默认 (使用全部核心)
+
+    Raises:
+        ValueError: 线程池创建失败
+    """
+    pass
+
+def get_num_threads() -> int:
+    """ 获取并行批处理函数当前使用的线程数
+
+    Returns:
+        int: 线程数
+    """
+    pass
+
+def content_hash(text: str, algo: str = 'xxh64') -> str:
+    """ 计算文本内容哈希, 用作 LLM 结果缓存等场景的键
+
+    Args:
+        text (str): 文本
+        algo (str, optional): 哈希算法, 'xxh64' / 'xxh3' 速度快, 'blake3' 为密码学哈希, 适合需要抗碰撞的场景. Defaults to 'xxh64'.
+
+    Raises:
+        ValueError: 未知的哈希算法
+
+    Returns:
+        str: 小写十六进制哈希, xxh64 / xxh3 为 16 位, blake3 为 64 位
+    """
+    pass
+
+def hash_batch(texts: list[str], algo: str = 'xxh64') -> list[str]:
+    """ 并行计算一批文本的内容哈希, 结果与逐个调用 content_hash 一致
+
+    Args:
+        texts (list[str]): 文本列表
+        algo (str, optional): 哈希算法, 同 content_hash. Defaults to 'xxh64'.
+
+    Raises:
+        ValueError: 未知的哈希算法
+
+    Returns:
+        list[str]: 与输入一一对应的哈希列表
+    """
+    pass
+
+class Cache:
+    """ 持久化的键值缓存, 用于按文本块哈希保存 LLM 抽取结果, 以 JSONL 追加写入单个文件
+    """
+
+    def __init__(self, path: str) -> None:
+        """ 打开缓存文件, 不存在时创建, 进程中断留下的不完整记录与无法解析 (包括不是 UTF-8) 的行会被忽略
+
+        Args:
+            path (str): 缓存文件路径
+
+        Raises:
+            ValueError: 文件无法读取或创建
+        """
+        pass
+
+    def get(self, key: str, default: Any = None) -> Any:
+        """ 读取缓存结果
+
+        Args:
+            key (str): 键, 通常为 content_hash 的结果
+            default (Any, optional): 未命中时的返回值. Defaults to None.
+
+        Returns:
+            Any: 缓存的结果
+        """
+        pass
+
+    def put(self, key: str, value: Any) -> None:
+        """ 写入缓存结果并立即落盘, 已存在的键会被覆盖
+
+        Args:
+            key (str): 键
+            value (Any): 可 JSON 序列化的结果
+
+        Raises:
+            ValueError: 结果无法序列化或写入失败
+        """
+        pass
+
+    def keys(self) -> list[str]:
+        """ 所有已缓存的键, 按字典序排列
+        """
+        pass
+
+    def compact(self) -> None:
+        """ 重写缓存文件, 去除被覆盖的旧记录
+
+        Raises:
+            ValueError: 写入失败
+        """
+        pass
+
+    def __contains__(self, key: str) -> bool:
+        pass
+
+    def __len__(self) -> int:
+        pass
+
+def run_pipeline(texts: list[str], steps: list[str], config: CoreConfig | None = None) -> list[str]:
+    """ 在一次调用中按顺序执行多个处理步骤, 省去逐个函数调用时 Python 与 Rust 之间的往返开销, 适用于大量短文本
+
+    可选步骤:
+        - 逐条处理 (参数取对应函数的默认值): 'clean_text', 'normalize_punct', 'normalize_cjk_spacing', 't2s', 'strip_footnotes' (即 handle_footnotes(mode='strip') 的正文), 'markdown_to_text', 'html_to_text'
+        - 'split_lines': 按行拆分, 去掉首尾空白并丢弃空行
+        - 'join_pdf_lines', 'optimize_length', 'merge': 将整个列表交给对应函数, 后两者的参数取自 config
+
+    Args:
+        texts (list[str]): 文本列表
+        steps (list[str]): 步骤名称列表
+        config (CoreConfig | None, optional): optimize_length 与 merge 使用的参数. Defaults to None.
+
+    Raises:
+        ValueError: 未知的步骤, 在执行任何步骤之前检查
+        ChunkingError: 分块参数不合法
+
+    Returns:
+        list[str]: 最后一个步骤的结果
+    """
+    pass
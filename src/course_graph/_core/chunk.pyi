@@ -0,0 +1,135 @@
+from collections.abc import Iterable
+
+from . import CoreConfig
+
+def optimize_length(s: list[str], n: int | None = None, config: CoreConfig | None = None) -> list[str]:
+    """ 将数组中的字符串到调整到目标长度附近
+
+    Args:
+        s (list[str]): 字符串数组
+        n (int | None, optional): 目标长度, 为 None 时取 config.chunk_size (默认 400). Defaults to None.
+        config (CoreConfig | None, optional): 默认参数, 其中的 delimiters 用于切分过长的字符串. Defaults to None.
+
+    Raises:
+        ChunkingError: n 不大于 0
+
+    Returns:
+        list[str]: 调整后的字符串数组
+    """
+    pass
+
+def merge(texts: list[str], n: int | None = None, config: CoreConfig | None = None) -> list[str]:
+    """ 将字符串数组合并到目标长度附近
+
+    Args:
+        texts (list[str]): 字符串数组
+        n (int | None, optional): 目标长度, 为 None 时取 config.chunk_size (默认 400). Defaults to None.
+        config (CoreConfig | None, optional): 默认参数, 其中的 delimiters 用于切分句子, overlap 为相邻块重叠的字符数. Defaults to None.
+
+    Raises:
+        ChunkingError: n 不大于 0, 或 overlap 不小于 n
+
+    Returns:
+        list[str]: 合并后的字符串数组
+    """
+    pass
+
+def optimize_length_file(path: str, n: int | None = None, config: CoreConfig | None = None) -> list[str]:
+    """ 以内存映射方式读取文本文件, 将各行作为字符串数组调用 optimize_length, 适用于数百 MB 的 OCR 结果等大文件
+
+    Args:
+        path (str): UTF-8 文本文件路径
+        n (int | None, optional): 目标长度, 同 optimize_length. Defaults to None.
+        config (CoreConfig | None, optional): 默认参数, 同 optimize_length. Defaults to None.
+
+    Raises:
+        ValueError: 文件无法读取或不是合法的 UTF-8
+        ChunkingError: n 不大于 0
+
+    Returns:
+        list[str]: 调整后的字符串数组, 与 optimize_length(open(path).read().splitlines(), ...) 一致
+    """
+    pass
+
+def merge_file(path: str, n: int | None = None, config: CoreConfig | None = None) -> list[str]:
+    """ 以内存映射方式读取文本文件, 将各行作为字符串数组调用 merge, 不会先将整个文件读入 Python 字符串
+
+    Args:
+        path (str): UTF-8 文本文件路径
+        n (int | None, optional): 目标长度, 同 merge. Defaults to None.
+        config (CoreConfig | None, optional): 默认参数, 同 merge. Defaults to None.
+
+    Raises:
+        ValueError: 文件无法读取或不是合法的 UTF-8
+        ChunkingError: n 不大于 0, 或 overlap 不小于 n
+
+    Returns:
+        list[str]: 合并后的字符串数组, 与 merge(open(path).read().splitlines(), ...) 一致
+    """
+    pass
+
+class ChunkBatch:
+    """ 文本块表, 列为 text (large_string), doc_id (large_string), start (int64), end (int64), page (int64, 可为空)
+
+    实现了 Arrow PyCapsule 接口, 可直接传给 pyarrow.record_batch / pyarrow.table / polars.DataFrame 而无需逐行转换
+    """
+    num_rows: int
+
+    def __len__(self) -> int:
+        pass
+
+    def __arrow_c_schema__(self) -> object:
+        pass
+
+    def __arrow_c_array__(self, requested_schema: object | None = None) -> tuple[object, object]:
+        pass
+
+    def __arrow_c_stream__(self, requested_schema: object | None = None) -> object:
+        pass
+
+    def to_pyarrow(self) -> object:
+        """ 转换为 pyarrow.RecordBatch, 需要安装 pyarrow
+
+        Returns:
+            pyarrow.RecordBatch: 记录批次
+        """
+        pass
+
+def chunks_to_arrow(chunks_with_metadata: Iterable[tuple[str, str, int, int, int | None]]) -> ChunkBatch:
+    """ 将带元数据的文本块一次性构建为 Arrow 记录批次, 供 Polars / Parquet 直接使用
+
+    Args:
+        chunks_with_metadata (Iterable[tuple[str, str, int, int, int | None]]): (文本, 文档编号, 起始偏移, 结束偏移, 页码) 序列, 页码未知时为 None
+
+    Raises:
+        ValueError: 某行不是五元组或类型不符
+
+    Returns:
+        ChunkBatch: 文本块表
+    """
+    pass
+
+def write_chunks(
+        path: str,
+        chunks: Iterable[str],
+        metadata: Iterable[dict | None] | None = None,
+        format: str = 'jsonl',
+        compression: str | None = None) -> int:
+    """ 将文本块逐行写入 JSONL 或 Parquet 文件, chunks 与 metadata 可为生成器, 写出的行不在内存中保留
+
+    JSONL 每行为 {"text": 文本, **元数据}; Parquet 包含 text 与 metadata 两列, metadata 为元数据的 JSON 字符串, 无元数据时为 null, 每 8192 行写出一个行组. 元数据中的 text 字段被忽略
+
+    Args:
+        path (str): 输出路径
+        chunks (Iterable[str]): 文本块
+        metadata (Iterable[dict | None] | None, optional): 与 chunks 一一对应的元数据. Defaults to None.
+        format (str, optional): 输出格式, 可选 'jsonl' / 'parquet'. Defaults to 'jsonl'.
+        compression (str | None, optional): 压缩方式, 可选 'none' / 'zstd', 为 None 时 Parquet 使用 zstd, JSONL 在路径以 .zst 结尾时使用 zstd. Defaults to None.
+
+    Raises:
+        ValueError: 格式或压缩方式不合法, metadata 与 chunks 数量不一致, 元数据无法转换为 JSON, 或文件无法写入
+
+    Returns:
+        int: 写入的行数
+    """
+    pass
@@ -0,0 +1,648 @@
+from . import CoreConfig
+
+def dedup_triples(triples: list[tuple[str, str, str]],
+        confidences: list[float] | None = None,
+        aggregate: str = 'max') -> list[tuple[str, str, str, int, float]]:
+    """ 三元组去重, 比较前统一全半角、大小写并压缩空白, 与中文相邻的空白忽略不计
+
+    重复三元组的置信度按 aggregate 聚合: max 取最大值, noisy_or 为 1 - ∏(1 - p), sum 为求和 (可用于累加支持计数)
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        confidences (list[float] | None, optional): 与三元组一一对应的置信度, 为 None 时均视为 1.0. Defaults to None.
+        aggregate (str, optional): 置信度聚合方式, 可选 'max' / 'noisy_or' / 'sum'. Defaults to 'max'.
+
+    Raises:
+        ValueError: 聚合方式不合法或置信度数量与三元组数量不一致
+
+    Returns:
+        list[tuple[str, str, str, int, float]]: (头实体, 关系, 尾实体, 出现次数, 置信度) 列表, 按首次出现的顺序, 保留首次出现时的写法
+    """
+    pass
+
+def merge_triples(
+        triples: list[tuple[str, str, str]],
+        entity_threshold: float | None = None,
+        relation_map: dict[str, str] | None = None,
+        confidences: list[float] | None = None,
+        aggregate: str = 'max',
+        config: CoreConfig | None = None) -> tuple[list[tuple[str, str, str, int, float]], dict[str, str]]:
+    """ 合并来自不同文本块的三元组: 归并近似重复的实体, 并按映射统一同义关系
+
+    首字相同且长度差可能达到阈值的两个实体, 归一化编辑距离相似度或字符二元组 Jaccard 系数达到阈值时视为同一实体,
+    每组实体以出现次数最多的写法为规范名; 比较期间释放 GIL
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        entity_threshold (float | None, optional): 实体相似度阈值, 为 None 时取 config.similarity_threshold (默认 0.85). Defaults to None.
+        relation_map (dict[str, str] | None, optional): 同义关系到规范关系的映射, 比较时统一全半角、大小写与空白. Defaults to None.
+        confidences (list[float] | None, optional): 与三元组一一对应的置信度, 为 None 时均视为 1.0. Defaults to None.
+        aggregate (str, optional): 合并后重复三元组的置信度聚合方式, 可选 'max' / 'noisy_or' / 'sum'. Defaults to 'max'.
+        config (CoreConfig | None, optional): 默认参数. Defaults to None.
+
+    Raises:
+        ValueError: 聚合方式不合法或置信度数量与三元组数量不一致
+
+    Returns:
+        tuple[list[tuple[str, str, str, int, float]], dict[str, str]]: (规范头实体, 规范关系, 规范尾实体, 出现次数, 置信度) 列表, 以及别名到规范名的映射
+    """
+    pass
+
+def normalize_relations(
+        triples: list[tuple[str, str, str]],
+        canonical_map: dict[str, list[str]],
+        default: str | None = None) -> tuple[list[tuple[str, str, str]], list[tuple[str, int]]]:
+    """ 将大模型生成的自由文本关系映射到封闭的关系集合
+
+    比较时统一全半角、大小写与空白; 同义词中的 "…" (或 "...") 匹配任意文本, 如 "由…组成" 可匹配 "由若干结点组成"
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        canonical_map (dict[str, list[str]]): 规范关系到其同义词列表的映射, 规范关系自身也会被识别
+        default (str | None, optional): 无法映射时使用的关系, 为 None 时丢弃该三元组. Defaults to None.
+
+    Returns:
+        tuple[list[tuple[str, str, str]], list[tuple[str, int]]]: 映射后的三元组, 以及未能映射的原始关系及其出现次数, 按次数降序
+    """
+    pass
+
+class KnowledgeGraph:
+    """ 内存中的知识图谱, 节点与关系名驻留为整数编号, 以紧凑的邻接表存储有向边
+    """
+
+    def __init__(self, triples: list[tuple[str, str, str]] | None = None) -> None:
+        """ 创建知识图谱
+
+        Args:
+            triples (list[tuple[str, str, str]] | None, optional): 初始的 (头实体, 关系, 尾实体) 列表. Defaults to None.
+        """
+        pass
+
+    def add_triple(self, head: str, relation: str, tail: str) -> bool:
+        """ 添加一条边, 节点不存在时自动创建
+
+        Args:
+            head (str): 头实体
+            relation (str): 关系
+            tail (str): 尾实体
+
+        Returns:
+            bool: 是否为新边, 完全相同的边只保留一条
+        """
+        pass
+
+    def add_triples(self, triples: list[tuple[str, str, str]]) -> int:
+        """ 批量添加边
+
+        Args:
+            triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+
+        Returns:
+            int: 新增的边数
+        """
+        pass
+
+    def has_node(self, node: str) -> bool:
+        pass
+
+    def neighbors(self, node: str, direction: str = 'out') -> list[tuple[str, str]]:
+        """ 查询节点的邻居
+
+        Args:
+            node (str): 节点
+            direction (str, optional): 'out' 为出边, 'in' 为入边, 'both' 为两者. Defaults to 'out'.
+
+        Raises:
+            ValueError: 节点不存在或方向未知
+
+        Returns:
+            list[tuple[str, str]]: (关系, 邻居节点) 列表, 按边的加入顺序
+        """
+        pass
+
+    def degree(self, node: str, direction: str = 'both') -> int:
+        """ 查询节点的度
+
+        Args:
+            node (str): 节点
+            direction (str, optional): 'out' 为出度, 'in' 为入度, 'both' 为两者之和. Defaults to 'both'.
+
+        Raises:
+            ValueError: 节点不存在或方向未知
+
+        Returns:
+            int: 度
+        """
+        pass
+
+    def remove_node(self, node: str) -> bool:
+        """ 删除节点及其全部边
+
+        Args:
+            node (str): 节点
+
+        Returns:
+            bool: 节点是否存在
+        """
+        pass
+
+    def merge_nodes(self, keep: str, other: str) -> None:
+        """ 将 other 合并到 keep: other 的边全部转移到 keep 上后删除 other, 两者之间的边被丢弃
+
+        Args:
+            keep (str): 保留的节点
+            other (str): 被合并的节点
+
+        Raises:
+            ValueError: 节点不存在
+        """
+        pass
+
+    def pagerank(self, damping: float = 0.85, iterations: int = 100, tol: float = 1e-6) -> list[tuple[str, float]]:
+        """ 计算全部节点的 PageRank, 同一对节点之间的多条边只计一次
+
+        Args:
+            damping (float, optional): 阻尼系数. Defaults to 0.85.
+            iterations (int, optional): 最大迭代次数. Defaults to 100.
+            tol (float, optional): 收敛阈值 (两次迭代得分差的 L1 范数). Defaults to 1e-6.
+
+        Raises:
+            ValueError: damping 不在 [0, 1] 内
+
+        Returns:
+            list[tuple[str, float]]: (节点, 得分) 列表, 按得分降序
+        """
+        pass
+
+    def save_graph(self, path: str, compress: bool = True) -> None:
+        """ 将图保存为二进制快照 (bincode, 可选 zstd 压缩), 重新加载时无需解析 JSON
+
+        已删除的节点不写入快照, 加载后节点重新编号, 节点与边的顺序不变
+
+        Args:
+            path (str): 文件路径
+            compress (bool, optional): 是否使用 zstd 压缩. Defaults to True.
+
+        Raises:
+            ValueError: 文件无法写入
+        """
+        pass
+
+    @staticmethod
+    def load_graph(path: str) -> KnowledgeGraph:
+        """ 从 save_graph 保存的二进制快照加载图
+
+        Args:
+            path (str): 文件路径
+
+        Raises:
+            ValueError: 文件无法读取或不是合法的快照
+
+        Returns:
+            KnowledgeGraph: 加载的图
+        """
+        pass
+
+    def nodes(self) -> list[str]:
+        """ 全部节点, 按加入顺序
+        """
+        pass
+
+    def triples(self) -> list[tuple[str, str, str]]:
+        """ 全部边, 以 (头实体, 关系, 尾实体) 表示
+        """
+        pass
+
+    def to_edge_list(self) -> list[tuple[str, str, str, dict[str, str]]]:
+        """ 导出 (头实体, 关系, 尾实体, 属性) 边列表, 属性为 {'relation': 关系}, 可直接用于 networkx:
+        nx.MultiDiGraph((h, t, attrs) for h, _, t, attrs in graph.to_edge_list())
+
+        同一节点名或关系名在各边间共享同一个字符串对象
+        """
+        pass
+
+    def node_count(self) -> int:
+        pass
+
+    def edge_count(self) -> int:
+        pass
+
+    def __len__(self) -> int:
+        pass
+
+    def __contains__(self, key: str) -> bool:
+        pass
+
+def to_cypher(triples: list[tuple[str, str, str]], node_label: str = 'Concept', batch_size: int = 1000) -> list[tuple[str, list[dict[str, str]]]]:
+    """ 生成将三元组导入 Neo4j 的参数化 Cypher 语句, 每条语句以 UNWIND $rows ... MERGE 批量写入同一种关系
+
+    节点以 name 属性去重, 关系名作为关系类型并已转义, 实体名只出现在参数中
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        node_label (str, optional): 节点标签. Defaults to 'Concept'.
+        batch_size (int, optional): 每条语句包含的三元组数量上限. Defaults to 1000.
+
+    Raises:
+        ValueError: batch_size 为 0 或 node_label 为空
+
+    Returns:
+        list[tuple[str, list[dict[str, str]]]]: (语句, 参数 rows) 列表, rows 中每项为 {'head': 头实体, 'tail': 尾实体}, 可依次以 session.run(statement, rows=rows) 执行
+    """
+    pass
+
+def to_neo4j_csv(triples: list[tuple[str, str, str]], out_dir: str, node_label: str = 'Concept') -> tuple[int, int]:
+    """ 按 neo4j-admin import 格式在 out_dir 下写出 nodes.csv 与 relationships.csv
+
+    节点按名称去重并分配 n0, n1, ... 形式的编号, 完全相同的三元组只写出一次
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        out_dir (str): 输出目录, 不存在时自动创建
+        node_label (str, optional): 节点标签. Defaults to 'Concept'.
+
+    Raises:
+        ValueError: 目录无法创建或文件无法写入
+
+    Returns:
+        tuple[int, int]: 节点数与关系数
+    """
+    pass
+
+def to_rdf(
+        triples: list[tuple[str, str, str]],
+        base_iri: str,
+        format: str = 'ntriples',
+        predicate_map: dict[str, str] | None = None,
+        lang: str = 'zh') -> str:
+    """ 将三元组导出为 RDF, 实体 IRI 为 base_iri 加实体名, 并为每个实体生成 rdfs:label
+
+    实体名中的中文等 Unicode 字符原样保留, 空白、保留字符与控制字符按 UTF-8 百分号编码
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        base_iri (str): 基础 IRI, 如 'http://example.org/course/'
+        format (str, optional): 'ntriples' (或 'nt') 与 'turtle' (或 'ttl'). Defaults to 'ntriples'.
+        predicate_map (dict[str, str] | None, optional): 关系到谓语 IRI 的映射, 未映射的关系使用 base_iri + 'relation/' + 关系名. Defaults to None.
+        lang (str, optional): rdfs:label 的语言标签, 为空时不加. Defaults to 'zh'.
+
+    Raises:
+        ValueError: 未知的格式
+
+    Returns:
+        str: 序列化后的 RDF 文本
+    """
+    pass
+
+def to_graphml(
+        triples: list[tuple[str, str, str]],
+        node_attrs: dict[str, dict[str, str]] | None = None,
+        format: str = 'graphml') -> str:
+    """ 将三元组导出为 GraphML 或 GEXF, 可直接在 Gephi、yEd 中打开
+
+    节点名作为 label 属性, 关系作为边的 relation 属性 (GEXF 中为边的 label)
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        node_attrs (dict[str, dict[str, str]] | None, optional): 节点名到其附加属性的映射, 如章节、类型. Defaults to None.
+        format (str, optional): 'graphml' 或 'gexf'. Defaults to 'graphml'.
+
+    Raises:
+        ValueError: 未知的格式
+
+    Returns:
+        str: XML 文本
+    """
+    pass
+
+def to_jsonld(
+        triples: list[tuple[str, str, str]],
+        context: dict | None = None,
+        base_iri: str = 'urn:coursekg:',
+        pretty: bool = False) -> str:
+    """ 将三元组导出为 JSON-LD 文档, 每个实体对应 @graph 中的一个节点对象, 关系作为属性, 取值为尾实体的 @id 列表
+
+    关系属性名为 'rel:' + 编码后的关系名, 展开为 base_iri + 'relation/' + 编码后的关系名, 不会与节点的 @id、name 冲突
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        context (dict | None, optional): @context 内容, 默认上下文定义 rel 前缀并将 name 映射到 rdfs:label; 指定时输出 [默认上下文, context], 其中可覆盖 rel 与 name. Defaults to None.
+        base_iri (str, optional): 实体 @id 的前缀, 实体 @id 为 base_iri + 'entity/' + 编码后的实体名. Defaults to 'urn:coursekg:'.
+        pretty (bool, optional): 是否缩进输出. Defaults to False.
+
+    Raises:
+        ValueError: context 中含有无法转换为 JSON 的值
+
+    Returns:
+        str: JSON-LD 文本
+    """
+    pass
+
+def find_cycles(edges: list[tuple[str, str]]) -> tuple[list[list[str]], list[tuple[str, str]]]:
+    """ 检测先修关系中的环, 并给出使图无环需要删除的边
+
+    删除的边由 Eades-Lin-Smyth 启发式求得后逐条尝试放回, 结果是极小的 (去掉其中任意一条都会留下环), 但不保证数量最少
+
+    Args:
+        edges (list[tuple[str, str]]): (先修概念, 后续概念) 有向边列表
+
+    Returns:
+        tuple[list[list[str]], list[tuple[str, str]]]: 每个含环的强连通分量中的一个环 (按环上顺序列出节点), 以及需要删除的边
+    """
+    pass
+
+def learning_path(
+        edges: list[tuple[str, str]],
+        targets: list[str],
+        chapter_order: dict[str, int] | None = None) -> list[str]:
+    """ 生成学习目标概念的学习路径: 对目标及其全部前驱组成的先修子图做拓扑排序
+
+    可同时学习的概念按章节序排列, 章节相同或未知时按在 edges 中首次出现的顺序
+
+    Args:
+        edges (list[tuple[str, str]]): (先修概念, 后续概念) 有向边列表
+        targets (list[str]): 目标概念
+        chapter_order (dict[str, int] | None, optional): 概念到章节序号的映射, 未给出的概念排在同批次最后. Defaults to None.
+
+    Raises:
+        ValueError: 先修子图中存在环, 可先用 find_cycles 处理
+
+    Returns:
+        list[str]: 按学习顺序排列的概念, 以目标概念结束
+    """
+    pass
+
+def subgraph(
+        triples: list[tuple[str, str, str]],
+        seeds: list[str],
+        k: int = 1,
+        direction: str = 'both') -> list[tuple[str, str, str]]:
+    """ 抽取种子概念 k 跳以内的子图, 即 k 跳内全部节点之间的三元组
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        seeds (list[str]): 种子概念, 不在图中的忽略
+        k (int, optional): 跳数. Defaults to 1.
+        direction (str, optional): 扩展方向, 'out' 沿边的方向, 'in' 逆着边的方向, 'both' 不区分方向. Defaults to 'both'.
+
+    Raises:
+        ValueError: 方向未知
+
+    Returns:
+        list[tuple[str, str, str]]: 子图中的三元组, 保持原有顺序
+    """
+    pass
+
+def components(
+        triples: list[tuple[str, str, str]],
+        entities: list[str] | None = None) -> tuple[list[list[str]], list[str]]:
+    """ 计算图的弱连通分量, 并列出孤立实体, 用于发现抽取结果中与主体不连通的碎片
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        entities (list[str] | None, optional): 额外的实体列表, 如抽取到但未出现在任何三元组中的实体. Defaults to None.
+
+    Returns:
+        tuple[list[list[str]], list[str]]: 各连通分量的节点 (按分量规模降序), 以及孤立实体 (没有与其他实体相连的边)
+    """
+    pass
+
+def pagerank(
+        edges: list[tuple[str, str]],
+        damping: float = 0.85,
+        iterations: int = 100,
+        tol: float = 1e-6) -> list[tuple[str, float]]:
+    """ 计算有向图的 PageRank, 用于排序核心概念, 没有出边的节点的得分均分给全部节点
+
+    Args:
+        edges (list[tuple[str, str]]): 有向边列表
+        damping (float, optional): 阻尼系数. Defaults to 0.85.
+        iterations (int, optional): 最大迭代次数. Defaults to 100.
+        tol (float, optional): 收敛阈值 (两次迭代得分差的 L1 范数). Defaults to 1e-6.
+
+    Raises:
+        ValueError: damping 不在 [0, 1] 内
+
+    Returns:
+        list[tuple[str, float]]: (节点, 得分) 列表, 按得分降序
+    """
+    pass
+
+def degree_centrality(edges: list[tuple[str, str]], direction: str = 'both') -> list[tuple[str, float]]:
+    """ 计算度中心性, 即度除以节点数减一
+
+    Args:
+        edges (list[tuple[str, str]]): 有向边列表, 重复边只计一次
+        direction (str, optional): 'out' 为出度, 'in' 为入度, 'both' 为两者之和. Defaults to 'both'.
+
+    Raises:
+        ValueError: 方向未知
+
+    Returns:
+        list[tuple[str, float]]: (节点, 中心性) 列表, 按中心性降序
+    """
+    pass
+
+def detect_communities(edges: list[tuple[str, str]], resolution: float = 1.0) -> list[list[str]]:
+    """ 使用 Louvain 算法将概念划分为主题社区, 可为缺少章节结构的课程建议模块划分
+
+    边视为无向边, 重复边累加权重; 节点按首次出现的顺序处理, 结果是确定的
+
+    Args:
+        edges (list[tuple[str, str]]): 边列表
+        resolution (float, optional): 分辨率, 越大社区越小. Defaults to 1.0.
+
+    Returns:
+        list[list[str]]: 各社区的节点, 按社区规模降序
+    """
+    pass
+
+def shortest_path(triples: list[tuple[str, str, str]],
+        src: str,
+        dst: str,
+        max_depth: int | None = None,
+        relations: list[str] | None = None,
+        direction: str = 'both') -> list[tuple[str, str, str]] | None:
+    """ 广度优先搜索两个概念之间的最短路径, 用于解释两个概念如何关联
+
+    Args:
+        triples (list[tuple[str, str, str]]): 三元组列表
+        src (str): 起点
+        dst (str): 终点
+        max_depth (int | None, optional): 最大跳数, 为 None 时不限制. Defaults to None.
+        relations (list[str] | None, optional): 仅沿这些关系搜索, 为 None 时不过滤. Defaults to None.
+        direction (str, optional): 搜索方向, 可选 'out' / 'in' / 'both'. Defaults to 'both'.
+
+    Raises:
+        ValueError: 方向不合法
+
+    Returns:
+        list[tuple[str, str, str]] | None: 路径上的三元组 (保持原方向), 不可达时返回 None
+    """
+    pass
+
+def diff_graphs(old_triples: list[tuple[str, str, str]],
+        new_triples: list[tuple[str, str, str]],
+        rename_threshold: float = 0.85) -> tuple[list[tuple[str, str, str]], list[tuple[str, str, str]], list[tuple[tuple[str, str, str], tuple[str, str, str]]], list[tuple[str, str]]]:
+    """ 比较两次抽取结果, 用于在提交前审阅提示词或模型变更对知识图谱的影响
+
+    三元组按规范形式比较; 仅在一侧出现的实体按编辑距离相似度配对为改名; 改名后相同或同一实体对上关系发生变化的三元组记为变更
+
+    Args:
+        old_triples (list[tuple[str, str, str]]): 旧三元组列表
+        new_triples (list[tuple[str, str, str]]): 新三元组列表
+        rename_threshold (float, optional): 实体改名的相似度阈值. Defaults to 0.85.
+
+    Returns:
+        tuple[list[tuple[str, str, str]], list[tuple[str, str, str]], list[tuple[tuple[str, str, str], tuple[str, str, str]]], list[tuple[str, str]]]: 新增三元组, 删除三元组, 变更的 (旧, 新) 三元组对, 实体改名 (旧名, 新名)
+    """
+    pass
+
+def merge_graphs(base: list[tuple[str, str, str, float]],
+        incoming: list[tuple[str, str, str, float]],
+        policy: str = 'keep_both') -> list[tuple[str, str, str, float]]:
+    """ 将新图增量合并到已有图中, 用于把各章节的图合并为课程图
+
+    三元组按规范形式比较, 相同三元组只保留一条并取最高置信度; 同一实体对之间的不同关系视为冲突, 按策略处理:
+    - keep_both: 全部保留
+    - prefer_confidence: 只保留置信度最高的关系, 同置信度时取后加入者
+    - prefer_newer: 实体对在新图中出现时只保留新图中的关系
+
+    Args:
+        base (list[tuple[str, str, str, float]]): 已有的 (头实体, 关系, 尾实体, 置信度) 列表
+        incoming (list[tuple[str, str, str, float]]): 新加入的 (头实体, 关系, 尾实体, 置信度) 列表
+        policy (str, optional): 冲突策略, 可选 'keep_both' / 'prefer_confidence' / 'prefer_newer'. Defaults to 'keep_both'.
+
+    Raises:
+        ValueError: 冲突策略不合法
+
+    Returns:
+        list[tuple[str, str, str, float]]: 合并后的三元组
+    """
+    pass
+
+def canonicalize_inverse(triples: list[tuple[str, str, str]], inverse_map: dict[str, str]) -> list[tuple[str, str, str]]:
+    """ 将逆关系改写为规范方向并去重, 避免 A-属于-B 与 B-包含-A 产生方向相反的重复边
+
+    比较时统一全半角、大小写与空白, 结果保留首次出现时的写法
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        inverse_map (dict[str, str]): 关系到其规范逆关系的映射, 如 {'属于': '包含'} 将 (A, 属于, B) 改写为 (B, 包含, A)
+
+    Raises:
+        ValueError: 映射中的规范关系本身又被映射
+
+    Returns:
+        list[tuple[str, str, str]]: 改写并去重后的三元组
+    """
+    pass
+
+def validate_triples(
+        triples: list[tuple[str, str, str]],
+        schema: dict[str, dict[str, list[str]]],
+        entity_types: dict[str, str] | None = None) -> list[tuple[int, str]]:
+    """ 按本体模式校验三元组, 在导入数据库前拒绝格式错误的大模型输出
+
+    关系名与实体名比较时统一全半角、大小写与空白
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+        schema (dict[str, dict[str, list[str]]]): 允许的关系到其约束的映射, 约束可含 'head' / 'tail' 两项, 分别为头、尾实体允许的类型, 缺省时不限制,
+            如 {'包含': {'head': ['课程', '章节'], 'tail': ['知识点']}, '相关': {}}
+        entity_types (dict[str, str] | None, optional): 实体到类型的映射. Defaults to None.
+
+    Raises:
+        ValueError: 约束中含 'head' / 'tail' 以外的键
+
+    Returns:
+        list[tuple[int, str]]: (三元组下标, 原因) 列表, 一个三元组可能有多条
+    """
+    pass
+
+def cooccurrence_graph(chunk_entities: list[list[str]], min_count: int = 2, window: int = 1) -> list[tuple[str, str, int]]:
+    """ 统计实体在文本块间的共现次数并生成带权边, 作为大模型关系抽取之外的廉价稠密关联
+
+    以每个文本块为锚点, 其中的实体与该块及其后 window - 1 个块中的实体构成共现, 同一锚点下每对实体只计一次
+
+    Args:
+        chunk_entities (list[list[str]]): 按顺序排列的各文本块中的实体
+        min_count (int, optional): 最小共现次数. Defaults to 2.
+        window (int, optional): 共现窗口的文本块数, 为 1 时只统计同一文本块内的共现. Defaults to 1.
+
+    Raises:
+        ValueError: window 为 0
+
+    Returns:
+        list[tuple[str, str, int]]: (实体, 实体, 共现次数) 列表, 按共现次数降序, 每对实体按首次出现的顺序排列
+    """
+    pass
+
+class GraphStats:
+    """ 图谱统计, 相同的三元组只计一次, 边视为有向边
+
+    density 为存在边的有序节点对占 n(n-1) 的比例, 同一对节点间的多种关系只计一次, 不含自环, 不超过 1
+    """
+    node_count: int
+    edge_count: int
+    density: float
+    degree_buckets: list[tuple[str, int]]
+    relation_counts: list[tuple[str, int]]
+    component_sizes: list[int]
+
+def graph_stats(triples: list[tuple[str, str, str]]) -> GraphStats:
+    """ 统计节点数、边数、密度、度数分布、关系频次与连通分量规模, 用于抽取报告页
+
+    度数按 2 的幂分桶, 标签形如 '1' / '2-3' / '4-7'; 关系频次按降序; 连通分量按弱连通计算, 规模降序
+
+    Args:
+        triples (list[tuple[str, str, str]]): (头实体, 关系, 尾实体) 列表
+
+    Returns:
+        GraphStats: 统计结果
+    """
+    pass
+
+def build_alias_table(
+        entities: list[str],
+        threshold: float | None = None,
+        config: CoreConfig | None = None) -> dict[str, str]:
+    """ 聚类近似重复的实体写法并为每组选出规范名, 生成整个流程共用的别名表
+
+    规范形式 (统一全半角、大小写与空白) 相同的写法直接归为一组; 其余按首字分块, 块内只比较长度差可能达到阈值的实体对,
+    归一化编辑距离相似度达到阈值即归为一组. 每组以出现次数最多的写法为规范名, 同频时取先出现者
+
+    Args:
+        entities (list[str]): 实体列表, 可含重复
+        threshold (float | None, optional): 相似度阈值, 为 None 时取 config.similarity_threshold (默认 0.85). Defaults to None.
+        config (CoreConfig | None, optional): 默认参数. Defaults to None.
+
+    Returns:
+        dict[str, str]: 别名到规范名的映射, 按别名排序, 不含规范名自身
+    """
+    pass
+
+def link_entities(
+        entities: list[str],
+        dictionary: dict[str, str],
+        fuzzy_threshold: float | None = None,
+        aliases: dict[str, str] | None = None,
+        config: CoreConfig | None = None) -> list[tuple[str, float, str] | None]:
+    """ 将抽取的概念链接到外部概念词典 (如知网概念编号)
+
+    依次尝试三个阶段:
+    - exact: 与词典中的名称完全相同
+    - alias: 经别名表 (如 build_alias_table 的结果) 映射为规范名后, 或统一全半角、大小写与空白后与词典名称相同
+    - fuzzy: 与词典名称的归一化编辑距离相似度达到阈值, 取得分最高者
+
+    Args:
+        entities (list[str]): 实体列表
+        dictionary (dict[str, str]): 概念名称到外部编号的映射
+        fuzzy_threshold (float | None, optional): 模糊匹配的相似度阈值, 为 None 时取 config.similarity_threshold (默认 0.85). Defaults to None.
+        aliases (dict[str, str] | None, optional): 别名到规范名的映射. Defaults to None.
+        config (CoreConfig | None, optional): 默认参数. Defaults to None.
+
+    Returns:
+        list[tuple[str, float, str] | None]: 与 entities 一一对应的 (外部编号, 得分, 匹配阶段), 未匹配时为 None
+    """
+    pass
@@ -0,0 +1,13 @@
+def get_latex_outline(tex: str) -> list[tuple[int, str]]:
+    """ 提取 LaTeX 中 \\part / \\chapter / \\section 等分节命令构成的大纲
+
+    级别与 parse_cn_heading 对应: part 0, chapter 1, section 2, subsection 3, subsubsection 4, paragraph 5, subparagraph 6.
+    忽略注释与 verbatim / lstlisting / minted 等环境, 标题中的格式命令只保留其参数, label / footnote 等命令连同参数去除
+
+    Args:
+        tex (str): LaTeX 源文本
+
+    Returns:
+        list[tuple[int, str]]: (标题级别, 标题文本) 列表
+    """
+    pass
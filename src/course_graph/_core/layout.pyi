@@ -0,0 +1,22 @@
+from . import CoreConfig
+
+def structure(
+        detections: list[tuple[str, tuple[float, ...]]],
+        iou_threshold: float | None = None,
+        config: CoreConfig | None = None,
+        stable: bool | None = None) -> list[tuple[str, tuple[float, ...]]]:
+    """ 检测结果后处理
+
+    Args:
+        detections (list[tuple[str, tuple[float, ...]]]): 检测结果
+        iou_threshold (float | None, optional): iou 阈值, 为 None 时取 config.iou_threshold (默认 0.1). Defaults to None.
+        config (CoreConfig | None, optional): 默认参数. Defaults to None.
+        stable (bool | None, optional): 两个检测框 IoU 超过阈值时是否总是保留靠前的一个, 否则随机保留其一; 为 None 时取 config.stable (默认 False). Defaults to None.
+
+    Raises:
+        LayoutError: iou_threshold 不在 [0, 1] 内, 或检测框坐标不合法
+
+    Returns:
+        list[tuple[str, tuple[float, ...]]]: 处理后检测结果
+    """
+    pass
@@ -0,0 +1,173 @@
+from . import CoreConfig
+
+def get_list(text: str) -> list:
+    """ 括号匹配提取列表
+
+    Args:
+        text (str): 待提取字符串
+
+    Raises:
+        ParseError: 列表括号未闭合, position 为最外层 '[' 的位置
+
+    Returns:
+        list: 列表, 文本中没有列表时为空列表
+    """
+    pass
+
+def parse_triples(text: str, format: str = 'auto') -> tuple[list[tuple[str, str, str]], list[str]]:
+    """ 解析大模型按提示词模板输出的三元组, 字段去除首尾空白与引号
+
+    支持的格式:
+    - tuple: 每行一个或多个 (头实体, 关系, 尾实体), 兼容全角括号与逗号, 不含括号的行以及正文中不含逗号的括号注释忽略
+    - json: 对象数组 (键为 head/relation/tail, subject/predicate/object 或 头实体/关系/尾实体) 或三元素数组的数组
+    - table: markdown 表格, 按表头识别列, 无可识别表头时取各行的三列
+    - auto: 含表格分隔行时按 table, 含 JSON 数组时按 json, 否则按 tuple
+
+    Args:
+        text (str): 大模型输出
+        format (str, optional): 格式, 可选 'auto' / 'tuple' / 'json' / 'table'. Defaults to 'auto'.
+
+    Raises:
+        ValueError: 格式不合法
+
+    Returns:
+        tuple[list[tuple[str, str, str]], list[str]]: 解析出的三元组, 以及逐行 (JSON 为逐项) 的错误说明
+    """
+    pass
+
+def parse_enumerated(text: str) -> list[str]:
+    """ 提取大模型回复中的列表项, 不限编号样式: 1. / 1) / 1、 / (1) / （一） / 一、 / ① / - / * / •
+
+    列表项之后缩进且不带编号的行视为该项的续行; 首个列表项之前的内容、空行之后以及顶格书写的说明文字被忽略
+
+    Args:
+        text (str): 大模型输出
+
+    Returns:
+        list[str]: 按顺序排列的列表项
+    """
+    pass
+
+def parse_key_values(text: str, keys: list[str]) -> dict[str, str]:
+    """ 提取 "名称：…" / "定义: …" 形式的字段, 用于解析概念卡片式的大模型回复
+
+    字段名后可跟中文或英文冒号, 允许前置列表标记与 markdown 加粗; 字段值可跨行, 直到下一个字段或空行为止;
+    同一字段出现多次时保留第一次
+
+    Args:
+        text (str): 大模型输出
+        keys (list[str]): 字段名列表, 如 ['名称', '定义', '别名']
+
+    Returns:
+        dict[str, str]: 字段名到值的映射, 按字段名排序, 不含未出现的字段
+    """
+    pass
+
+def validate_extraction(
+        json_str: str,
+        required_fields: list[str],
+        field_types: dict[str, str] | None = None,
+        allow_extra: bool = False) -> list[str]:
+    """ 解析并校验大模型输出的 JSON, 报告缺失、多余与类型错误的字段及其路径, 便于在重试提示词中给出准确反馈
+
+    顶层为数组时逐项校验; 字段名可用 "a.b" 表示嵌套字段; 整体解析失败时尝试解析首个括号与末个括号之间的部分
+
+    Args:
+        json_str (str): 大模型输出
+        required_fields (list[str]): 必需字段
+        field_types (dict[str, str] | None, optional): 字段到类型的映射, 类型可选 str / int / float / bool / list / dict / null 及 list[T]. Defaults to None.
+        allow_extra (bool, optional): 是否允许出现 required_fields 与 field_types 之外的顶层字段. Defaults to False.
+
+    Raises:
+        ValueError: 类型名不合法
+
+    Returns:
+        list[str]: 错误说明, 形如 "$[0].name: 缺少字段", 全部通过时为空列表
+    """
+    pass
+
+def isolate_structured(text: str) -> str:
+    """ 去除大模型回复中的开场白 ("好的，以下是提取结果：") 与结束语, 只保留结构化内容
+
+    依次尝试:
+    1. 代码块: 返回首个标注了语言的代码块内容, 都未标注时返回首个代码块
+    2. JSON: 返回首个括号配对完整且能解析的 JSON 对象或数组
+    3. 按空行分段, 去掉开头的开场白段与末尾的结束语段; 只有一段时逐行处理
+
+    Args:
+        text (str): 大模型输出
+
+    Returns:
+        str: 结构化内容
+    """
+    pass
+
+def get_all_json(text: str) -> list[dict | list]:
+    """ 按顺序提取文本中全部顶层 JSON 对象与数组, 适用于每个概念输出一个对象或 JSON Lines 格式的回复
+
+    括号配对完整但无法解析的片段会被跳过, 并继续在其内部查找
+
+    Args:
+        text (str): 大模型输出
+
+    Returns:
+        list[dict | list]: 解析出的 JSON 值
+    """
+    pass
+
+def get_yaml_from_string(text: str) -> dict | list | str | int | float | bool | None:
+    """ 提取并解析大模型回复中的 YAML
+
+    优先取 yaml/yml 代码块; 没有代码块时先将全文作为 YAML 解析, 失败后只去除首尾的开场白与结束语段落再解析; 行首的制表符按两个空格缩进处理
+
+    Args:
+        text (str): 大模型输出
+
+    Raises:
+        ParseError: YAML 解析失败, 或映射的键不是字符串, position 为 YAML 块内的位置
+
+    Returns:
+        dict | list | str | int | float | bool | None: 解析结果
+    """
+    pass
+
+def dedup_items(
+        items: list[str],
+        threshold: float | None = None,
+        config: CoreConfig | None = None) -> tuple[list[str], list[tuple[str, str]]]:
+    """ 去除列表中近似重复的项, 如 "二叉树" 与 "二叉树。", 保留首次出现的项并保持顺序
+
+    比较前去除首尾标点并统一全半角、大小写与空白; 归一化编辑距离相似度达到阈值,
+    或一方包含另一方且长度比达到阈值时视为重复; 空项被丢弃
+
+    Args:
+        items (list[str]): 列表项
+        threshold (float | None, optional): 相似度阈值, 为 None 时取 config.similarity_threshold (默认 0.85). Defaults to None.
+        config (CoreConfig | None, optional): 默认参数. Defaults to None.
+
+    Returns:
+        tuple[list[str], list[tuple[str, str]]]: 去重后的列表, 以及 (被去除的项, 保留的项) 列表
+    """
+    pass
+
+def parse_batch(outputs: list[str], parser: str, options: dict | None = None) -> list[tuple[list | dict, list[str]]]:
+    """ 释放 GIL 并行解析大量大模型输出
+
+    解析器:
+    - list: 同 parse_enumerated, 结果为 list[str]
+    - json: 同 get_all_json, 结果为 list
+    - triples: 同 parse_triples, 可用选项 format 指定格式, 结果为 list[tuple[str, str, str]]
+    - key_values: 同 parse_key_values, 必须用选项 keys 指定字段名, 结果为 dict[str, str]
+
+    Args:
+        outputs (list[str]): 大模型输出列表
+        parser (str): 解析器, 可选 'list' / 'json' / 'triples' / 'key_values'
+        options (dict | None, optional): 解析选项, 可含 'format' 与 'keys'. Defaults to None.
+
+    Raises:
+        ValueError: 解析器或选项不合法
+
+    Returns:
+        list[tuple[list | dict, list[str]]]: 与 outputs 一一对应的 (解析结果, 错误说明), 未解析出内容或缺少字段时错误说明非空
+    """
+    pass
@@ -18,7 +18,7 @@ from .utils import instance_method_transactional
 from ..resource import ResourceMap
 from .types import BookMark, KPEntity, KPRelation, ContentType
 from tqdm import tqdm
-from course_graph._core import merge
+from course_graph._core.chunk import merge
 from ..database import Neo4j
 from py2neo import Node, Relationship
 
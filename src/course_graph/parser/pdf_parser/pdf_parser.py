@@ -18,7 +18,8 @@ from ...llm import LLM
 from ...llm.prompt import VLPromptGenerator, ParserPromptGenerator
 import os
 import shutil
-from course_graph._core import get_list, get_longest_seq
+from course_graph._core import get_longest_seq
+from course_graph._core.parse import get_list
 from ..types import BookMark, PageIndex
 from shuangchentools.utils.file import clear_directory
 from typing import Callable
@@ -11,7 +11,7 @@ from paddleocr.ppstructure.recovery.recovery_to_doc import sorted_layout_boxes
 from paddleocr import PPStructure
 from doclayout_yolo import YOLOv10
 import json
-from course_graph._core import structure
+from course_graph._core.layout import structure
 
 
 class StructureResult(TypedDict, total=False):
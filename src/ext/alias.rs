@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::config::CoreConfig;
+use super::dedup::find_root;
+use super::similarity::{max_length_diff, normalized_similarity};
+use super::threads::install;
+use super::triples::normalize_key;
+
+#[pyfunction]
+#[pyo3(signature = (entities, threshold=None, config=None))]
+pub fn build_alias_table(
+    py: Python<'_>,
+    entities: Vec<String>,
+    threshold: Option<f64>,
+    config: Option<CoreConfig>,
+) -> PyResult<BTreeMap<String, String>> {
+    let threshold = threshold.unwrap_or_else(|| config.unwrap_or_default().similarity_threshold);
+    Ok(py.allow_threads(|| alias_table(&entities, threshold)))
+}
+
+fn alias_table(entities: &[String], threshold: f64) -> BTreeMap<String, String> {
+    // 规范形式相同的写法先归并, 记录各写法的出现次数
+    let mut key_index: HashMap<String, usize> = HashMap::new();
+    let mut keys: Vec<Vec<char>> = Vec::new();
+    let mut surfaces: Vec<Vec<(String, usize)>> = Vec::new();
+    for entity in entities {
+        let entity = entity.trim();
+        if entity.is_empty() {
+            continue;
+        }
+        let key = normalize_key(entity);
+        let i = *key_index.entry(key.clone()).or_insert_with(|| {
+            keys.push(key.chars().collect());
+            surfaces.push(Vec::new());
+            keys.len() - 1
+        });
+        match surfaces[i].iter_mut().find(|(s, _)| s == entity) {
+            Some((_, count)) => *count += 1,
+            None => surfaces[i].push((entity.to_string(), 1)),
+        }
+    }
+
+    // 按首字分块, 块内按长度排序, 只比较长度差可能达到阈值的对
+    let mut blocks: HashMap<char, Vec<usize>> = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        blocks.entry(key[0]).or_default().push(i);
+    }
+    let pairs: Vec<(usize, usize)> = install(|| {
+        blocks
+            .into_par_iter()
+            .flat_map_iter(|(_, mut block)| {
+                block.sort_by_key(|&i| (keys[i].len(), i));
+                let mut pairs = Vec::new();
+                for (x, &i) in block.iter().enumerate() {
+                    let limit = keys[i].len() + max_length_diff(keys[i].len(), threshold);
+                    for &j in block[x + 1..]
+                        .iter()
+                        .take_while(|&&j| keys[j].len() <= limit)
+                    {
+                        if normalized_similarity(&keys[i], &keys[j], threshold) >= threshold {
+                            pairs.push((i, j));
+                        }
+                    }
+                }
+                pairs
+            })
+            .collect()
+    });
+    let mut parent: Vec<usize> = (0..keys.len()).collect();
+    for (i, j) in pairs {
+        let (ri, rj) = (find_root(&mut parent, i), find_root(&mut parent, j));
+        parent[ri.max(rj)] = ri.min(rj);
+    }
+
+    // 每组取出现次数最多的写法为规范名, 同频时取先出现者
+    let mut groups: HashMap<usize, Vec<&(String, usize)>> = HashMap::new();
+    for (i, group) in surfaces.iter().enumerate() {
+        groups
+            .entry(find_root(&mut parent, i))
+            .or_default()
+            .extend(group);
+    }
+    let mut aliases = BTreeMap::new();
+    for group in groups.into_values() {
+        let canonical = group
+            .iter()
+            .fold(group[0], |best, s| if s.1 > best.1 { s } else { best });
+        for (surface, _) in group.iter().copied().filter(|s| s.0 != canonical.0) {
+            aliases.insert(surface.clone(), canonical.0.clone());
+        }
+    }
+    aliases
+}
+
+#[pyfunction]
+#[pyo3(signature = (entities, dictionary, fuzzy_threshold=None, aliases=None, config=None))]
+pub fn link_entities(
+    py: Python<'_>,
+    entities: Vec<String>,
+    dictionary: HashMap<String, String>,
+    fuzzy_threshold: Option<f64>,
+    aliases: Option<HashMap<String, String>>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<Option<(String, f64, &'static str)>>> {
+    let fuzzy_threshold =
+        fuzzy_threshold.unwrap_or_else(|| config.unwrap_or_default().similarity_threshold);
+    let aliases = aliases.unwrap_or_default();
+    let mut normalized: HashMap<String, &str> = HashMap::new();
+    let mut names: Vec<(&str, Vec<char>)> = Vec::with_capacity(dictionary.len());
+    let mut sorted: Vec<(&String, &String)> = dictionary.iter().collect();
+    sorted.sort();
+    for (name, id) in sorted {
+        let key = normalize_key(name);
+        normalized.entry(key.clone()).or_insert(id);
+        names.push((id, key.chars().collect()));
+    }
+    // 按长度排序, 模糊匹配时只比较长度差可能达到阈值的词条
+    names.sort_by_key(|(_, key)| key.len());
+
+    let link = |entity: &String| -> Option<(String, f64, &'static str)> {
+        let entity = entity.trim();
+        if let Some(id) = dictionary.get(entity) {
+            return Some((id.clone(), 1.0, "exact"));
+        }
+        let canonical = aliases.get(entity).map_or(entity, String::as_str);
+        let key = normalize_key(canonical);
+        if let Some(id) = dictionary
+            .get(canonical)
+            .map(String::as_str)
+            .or_else(|| normalized.get(&key).copied())
+        {
+            return Some((id.to_string(), 1.0, "alias"));
+        }
+
+        let key: Vec<char> = key.chars().collect();
+        let low = (key.len() as f64 * fuzzy_threshold - 1e-9).ceil() as usize;
+        let high = key.len() + max_length_diff(key.len(), fuzzy_threshold);
+        let start = names.partition_point(|(_, name)| name.len() < low);
+        let mut best: Option<(&str, f64)> = None;
+        for (id, name) in names[start..]
+            .iter()
+            .take_while(|(_, name)| name.len() <= high)
+        {
+            let cutoff = best.map_or(fuzzy_threshold, |(_, s)| s.max(fuzzy_threshold));
+            let score = normalized_similarity(&key, name, cutoff);
+            if score >= fuzzy_threshold && best.is_none_or(|(_, s)| score > s) {
+                best = Some((id, score));
+            }
+        }
+        best.map(|(id, score)| (id.to_string(), score, "fuzzy"))
+    };
+    Ok(py.allow_threads(|| install(|| entities.par_iter().map(link).collect())))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(build_alias_table, m)?)?;
+    m.add_function(wrap_pyfunction!(link_entities, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use std::ffi::{c_char, c_int, c_void, CString};
+
+use arrow_buffer::{Buffer, NullBuffer};
+use arrow_data::ffi::FFI_ArrowArray;
+use arrow_data::ArrayData;
+use arrow_schema::ffi::FFI_ArrowSchema;
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyCapsule, PyString, PyTuple};
+
+fn arrow_error(e: ArrowError) -> PyErr {
+    PyValueError::new_err(format!("Arrow 数据构建失败: {}", e))
+}
+
+fn chunk_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("text", DataType::LargeUtf8, false),
+        Field::new("doc_id", DataType::LargeUtf8, false),
+        Field::new("start", DataType::Int64, false),
+        Field::new("end", DataType::Int64, false),
+        Field::new("page", DataType::Int64, true),
+    ])
+}
+
+// 按 Arrow 的变长字符串布局逐行追加, 避免先收集为 Vec<String>
+#[derive(Default)]
+struct StringColumn {
+    offsets: Vec<i64>,
+    values: Vec<u8>,
+}
+
+impl StringColumn {
+    fn push(&mut self, value: &str) {
+        if self.offsets.is_empty() {
+            self.offsets.push(0);
+        }
+        self.values.extend_from_slice(value.as_bytes());
+        self.offsets.push(self.values.len() as i64);
+    }
+
+    fn finish(mut self, len: usize) -> Result<ArrayData, ArrowError> {
+        if self.offsets.is_empty() {
+            self.offsets.push(0);
+        }
+        ArrayData::builder(DataType::LargeUtf8)
+            .len(len)
+            .add_buffer(Buffer::from_vec(self.offsets))
+            .add_buffer(Buffer::from_vec(self.values))
+            .build()
+    }
+}
+
+fn int_column(values: Vec<i64>, nulls: Option<NullBuffer>) -> Result<ArrayData, ArrowError> {
+    ArrayData::builder(DataType::Int64)
+        .len(values.len())
+        .add_buffer(Buffer::from_vec(values))
+        .nulls(nulls)
+        .build()
+}
+
+// Arrow C Stream 接口的结构体, arrow-data 只提供了 C Data 接口
+#[repr(C)]
+struct FFI_ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut Self, *mut FFI_ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut Self, *mut FFI_ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut Self) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut Self)>,
+    private_data: *mut c_void,
+}
+
+// 仅通过 PyCapsule 在持有 GIL 时转移所有权
+unsafe impl Send for FFI_ArrowArrayStream {}
+
+// 流中只有一个批次, 取出后返回已释放的数组表示结束
+struct StreamState {
+    schema: Schema,
+    batch: Option<ArrayData>,
+}
+
+const EIO: c_int = 5;
+
+unsafe extern "C" fn stream_get_schema(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowSchema,
+) -> c_int {
+    let state = &*((*stream).private_data as *const StreamState);
+    match FFI_ArrowSchema::try_from(&state.schema) {
+        Ok(schema) => {
+            std::ptr::write(out, schema);
+            0
+        }
+        Err(_) => EIO,
+    }
+}
+
+unsafe extern "C" fn stream_get_next(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowArray,
+) -> c_int {
+    let state = &mut *((*stream).private_data as *mut StreamState);
+    let array = match state.batch.take() {
+        Some(batch) => FFI_ArrowArray::new(&batch),
+        None => FFI_ArrowArray::empty(),
+    };
+    std::ptr::write(out, array);
+    0
+}
+
+unsafe extern "C" fn stream_get_last_error(_: *mut FFI_ArrowArrayStream) -> *const c_char {
+    std::ptr::null()
+}
+
+unsafe extern "C" fn stream_release(stream: *mut FFI_ArrowArrayStream) {
+    let stream = &mut *stream;
+    drop(Box::from_raw(stream.private_data as *mut StreamState));
+    stream.private_data = std::ptr::null_mut();
+    stream.release = None;
+}
+
+impl FFI_ArrowArrayStream {
+    fn new(schema: Schema, batch: ArrayData) -> Self {
+        let state = Box::new(StreamState {
+            schema,
+            batch: Some(batch),
+        });
+        FFI_ArrowArrayStream {
+            get_schema: Some(stream_get_schema),
+            get_next: Some(stream_get_next),
+            get_last_error: Some(stream_get_last_error),
+            release: Some(stream_release),
+            private_data: Box::into_raw(state) as *mut c_void,
+        }
+    }
+}
+
+// 消费方转移所有权后会将 release 置空, 未被消费时在此释放
+impl Drop for FFI_ArrowArrayStream {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) };
+        }
+    }
+}
+
+fn capsule_name(name: &str) -> Option<CString> {
+    CString::new(name).ok()
+}
+
+#[pyclass(frozen)]
+pub struct ChunkBatch {
+    schema: Schema,
+    data: ArrayData,
+}
+
+#[pymethods]
+impl ChunkBatch {
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ChunkBatch(num_rows={}, columns=[text, doc_id, start, end, page])",
+            self.data.len()
+        )
+    }
+
+    fn __arrow_c_schema__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyCapsule>> {
+        let schema = FFI_ArrowSchema::try_from(&self.schema).map_err(arrow_error)?;
+        PyCapsule::new_bound(py, schema, capsule_name("arrow_schema"))
+    }
+
+    // requested_schema 仅作提示, 按协议可以忽略
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Bound<'py, PyTuple>> {
+        let _ = requested_schema;
+        let schema = self.__arrow_c_schema__(py)?;
+        let array = PyCapsule::new_bound(
+            py,
+            FFI_ArrowArray::new(&self.data),
+            capsule_name("arrow_array"),
+        )?;
+        Ok(PyTuple::new_bound(
+            py,
+            [schema.into_any(), array.into_any()],
+        ))
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let stream = FFI_ArrowArrayStream::new(self.schema.clone(), self.data.clone());
+        PyCapsule::new_bound(py, stream, capsule_name("arrow_array_stream"))
+    }
+
+    fn to_pyarrow<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        slf.py()
+            .import_bound("pyarrow")?
+            .call_method1("record_batch", (slf,))
+    }
+}
+
+#[pyfunction]
+pub fn chunks_to_arrow(chunks_with_metadata: &Bound<'_, PyAny>) -> PyResult<ChunkBatch> {
+    let mut text = StringColumn::default();
+    let mut doc_id = StringColumn::default();
+    let mut start = Vec::new();
+    let mut end = Vec::new();
+    let mut page = Vec::new();
+    let mut valid = Vec::new();
+
+    for (i, row) in chunks_with_metadata.iter()?.enumerate() {
+        let (t, d, s, e, p): (
+            Bound<'_, PyString>,
+            Bound<'_, PyString>,
+            i64,
+            i64,
+            Option<i64>,
+        ) = row?.extract().map_err(|err| {
+            PyValueError::new_err(format!(
+                "第 {} 行应为 (text, doc_id, start, end, page): {}",
+                i, err
+            ))
+        })?;
+        text.push(&t.to_cow()?);
+        doc_id.push(&d.to_cow()?);
+        start.push(s);
+        end.push(e);
+        page.push(p.unwrap_or(0));
+        valid.push(p.is_some());
+    }
+
+    let len = start.len();
+    let nulls = valid.contains(&false).then(|| NullBuffer::from(valid));
+    let columns = vec![
+        text.finish(len).map_err(arrow_error)?,
+        doc_id.finish(len).map_err(arrow_error)?,
+        int_column(start, None).map_err(arrow_error)?,
+        int_column(end, None).map_err(arrow_error)?,
+        int_column(page, nulls).map_err(arrow_error)?,
+    ];
+    let schema = chunk_schema();
+    let data = ArrayData::builder(DataType::Struct(schema.fields().clone()))
+        .len(len)
+        .child_data(columns)
+        .build()
+        .map_err(arrow_error)?;
+    Ok(ChunkBatch { schema, data })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ChunkBatch>()?;
+    m.add_function(wrap_pyfunction!(chunks_to_arrow, m)?)?;
+    Ok(())
+}
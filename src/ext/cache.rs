@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde_json::Value;
+
+use super::json::{json_to_py, py_to_json};
+
+fn io_error(path: &str, e: std::io::Error) -> PyErr {
+    PyValueError::new_err(format!("无法访问缓存文件 {}: {}", path, e))
+}
+
+// 以追加写入的 JSONL 文件持久化, 每行为 [键, 值], 同一键以最后一次写入为准
+#[pyclass]
+pub struct Cache {
+    path: String,
+    entries: HashMap<String, Value>,
+    file: BufWriter<File>,
+    // 文件中被后续写入覆盖的行数, 供 compact 判断
+    stale: usize,
+}
+
+// 已有记录, 被覆盖的行数, 以及文件末尾是否缺少换行
+type Loaded = (HashMap<String, Value>, usize, bool);
+
+// 读取已有记录, 进程中断可能留下不完整的末行, 跳过无法解析或不是 UTF-8 的行
+fn load_entries(path: &str) -> PyResult<Loaded> {
+    let mut entries = HashMap::new();
+    let mut stale = 0;
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((entries, stale, false)),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    let mut needs_newline = false;
+    loop {
+        line.clear();
+        if reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| io_error(path, e))?
+            == 0
+        {
+            break;
+        }
+        needs_newline = !line.ends_with(b"\n");
+        let Ok(Value::Array(mut pair)) = serde_json::from_slice::<Value>(&line) else {
+            continue;
+        };
+        if pair.len() != 2 {
+            continue;
+        }
+        let value = pair.pop().unwrap();
+        let Value::String(key) = pair.pop().unwrap() else {
+            continue;
+        };
+        if entries.insert(key, value).is_some() {
+            stale += 1;
+        }
+    }
+    Ok((entries, stale, needs_newline))
+}
+
+fn open_append(path: &str) -> PyResult<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io_error(path, e))?;
+    Ok(BufWriter::new(file))
+}
+
+fn write_entry<W: Write>(writer: &mut W, key: &str, value: &Value) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *writer, &(key, value))?;
+    writer.write_all(b"\n")
+}
+
+#[pymethods]
+impl Cache {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        let (entries, stale, needs_newline) = load_entries(path)?;
+        let mut file = open_append(path)?;
+        // 末行不完整时补一个换行, 避免与新记录拼接
+        if needs_newline {
+            file.write_all(b"\n").map_err(|e| io_error(path, e))?;
+        }
+        Ok(Cache {
+            path: path.to_string(),
+            entries,
+            file,
+            stale,
+        })
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+        match self.entries.get(key) {
+            Some(value) => json_to_py(py, value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    // 每次写入后立即落盘, 中途中断时已完成的结果不会丢失
+    fn put(&mut self, key: String, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = py_to_json(value)?;
+        write_entry(&mut self.file, &key, &value)
+            .and_then(|_| self.file.flush())
+            .map_err(|e| io_error(&self.path, e))?;
+        if self.entries.insert(key, value).is_some() {
+            self.stale += 1;
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.entries.keys().cloned().collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    // 重写缓存文件, 去除被覆盖的旧记录
+    fn compact(&mut self) -> PyResult<()> {
+        if self.stale == 0 {
+            return Ok(());
+        }
+        let tmp = format!("{}.tmp", self.path);
+        let mut writer = BufWriter::new(File::create(&tmp).map_err(|e| io_error(&tmp, e))?);
+        let mut entries: Vec<(&String, &Value)> = self.entries.iter().collect();
+        entries.sort_unstable_by_key(|&(key, _)| key);
+        for (key, value) in entries {
+            write_entry(&mut writer, key, value).map_err(|e| io_error(&tmp, e))?;
+        }
+        writer.flush().map_err(|e| io_error(&tmp, e))?;
+        drop(writer);
+        fs::rename(&tmp, &self.path).map_err(|e| io_error(&self.path, e))?;
+        self.file = open_append(&self.path)?;
+        self.stale = 0;
+        Ok(())
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Cache>()?;
+    Ok(())
+}
@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2A6DF}'
+        | '\u{3000}'..='\u{303F}'
+        | '\u{FF00}'..='\u{FFEF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{AC00}'..='\u{D7AF}')
+}
+
+const SENTENCE_END: [char; 10] = ['。', '！', '？', '!', '?', '；', '：', '”', '…', '.'];
+
+#[pyfunction]
+pub fn join_pdf_lines(lines: Vec<String>) -> PyResult<Vec<String>> {
+    let mut result = Vec::new();
+    let mut paragraph = String::new();
+    // 以最长行近似版心宽度, 明显短于它且以句末标点结尾的行视为段落结束
+    let max_len = lines
+        .iter()
+        .map(|l| l.trim().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for line in &lines {
+        let line = line.trim();
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                result.push(std::mem::take(&mut paragraph));
+            }
+            continue;
+        }
+
+        if let (Some(last), Some(first)) = (paragraph.chars().last(), line.chars().next()) {
+            let before_hyphen = paragraph.chars().rev().nth(1);
+            if last == '-'
+                && before_hyphen.is_some_and(|c| c.is_ascii_alphabetic())
+                && first.is_lowercase()
+            {
+                // 英文断词连字符
+                paragraph.pop();
+            } else if !is_cjk(last) && !is_cjk(first) {
+                paragraph.push(' ');
+            }
+        }
+        paragraph.push_str(line);
+
+        let len = line.chars().count();
+        if line.ends_with(SENTENCE_END) && len * 5 < max_len * 4 {
+            result.push(std::mem::take(&mut paragraph));
+        }
+    }
+    if !paragraph.is_empty() {
+        result.push(paragraph);
+    }
+
+    Ok(result)
+}
+
+fn is_inline_space(c: char) -> bool {
+    c.is_whitespace() && c != '\n' && c != '\r'
+}
+
+#[pyfunction]
+pub fn normalize_cjk_spacing(text: &str) -> PyResult<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+    let mut prev: Option<char> = None;
+
+    while let Some((start, c)) = chars.next() {
+        if !is_inline_space(c) {
+            result.push(c);
+            prev = Some(c);
+            continue;
+        }
+        // 找到空白串的结尾
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, n)) = chars.peek() {
+            if !is_inline_space(n) {
+                break;
+            }
+            end = i + n.len_utf8();
+            chars.next();
+        }
+        let next = chars.peek().map(|&(_, n)| n);
+        // 两侧均为中文字符时删除空白
+        if !(prev.is_some_and(is_cjk) && next.is_some_and(is_cjk)) {
+            result.push_str(&text[start..end]);
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn to_half_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap(),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+fn half_punct(c: char) -> char {
+    match c {
+        '（' => '(',
+        '）' => ')',
+        '【' | '〔' | '［' => '[',
+        '】' | '〕' | '］' => ']',
+        '“' | '”' | '「' | '」' | '『' | '』' | '＂' => '"',
+        '‘' | '’' | '＇' => '\'',
+        '，' | '、' => ',',
+        '。' => '.',
+        '：' => ':',
+        '；' => ';',
+        '！' => '!',
+        '？' => '?',
+        _ => to_half_width(c),
+    }
+}
+
+fn zh_punct(c: char) -> char {
+    match c {
+        ',' => '，',
+        '.' => '。',
+        ':' => '：',
+        ';' => '；',
+        '!' => '！',
+        '?' => '？',
+        '(' => '（',
+        ')' => '）',
+        '[' | '〔' | '［' => '【',
+        ']' | '〕' | '］' => '】',
+        _ => c,
+    }
+}
+
+// 连续 3 个及以上的 '.' (含全角) 视为省略号, 转为 "……", 以免被逐个转为句号后折叠
+fn ellipsis_to_zh(chars: &[char]) -> Vec<char> {
+    let mut result = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let run = chars[i..]
+            .iter()
+            .take_while(|&&c| c == '.' || c == '．')
+            .count();
+        if run >= 3 {
+            result.extend(['…', '…']);
+            i += run;
+        } else {
+            result.push(chars[i]);
+            i += 1;
+        }
+    }
+    result
+}
+
+const COLLAPSIBLE: [char; 13] = [
+    '，', '。', '！', '？', '；', '：', '、', ',', '!', '?', ';', ':', '～',
+];
+
+#[pyfunction]
+#[pyo3(signature = (text, mode = "zh"))]
+pub fn normalize_punct(text: &str, mode: &str) -> PyResult<String> {
+    if mode != "zh" && mode != "half" {
+        return Err(PyValueError::new_err(format!(
+            "未知的 mode: {}, 可选 zh/half",
+            mode
+        )));
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    if mode == "zh" {
+        chars = ellipsis_to_zh(&chars);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut double_open = true;
+    let mut single_open = true;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let mut c = if mode == "half" {
+            half_punct(c)
+        } else {
+            // 全角字母数字转半角, 标点保持中文形式
+            let h = to_half_width(c);
+            if h.is_ascii_alphanumeric() || h == ' ' {
+                h
+            } else {
+                c
+            }
+        };
+
+        if mode == "zh" {
+            let prev_cjk = result.chars().last().is_some_and(is_cjk);
+            let next_cjk = chars.get(i + 1).is_some_and(|&n| is_cjk(n));
+            match c {
+                // 中文语境中的英文标点转为中文标点, 避开 "3.14" 这样的数字
+                ',' | '.' | ':' | ';' | '!' | '?' | '(' | ')' | '[' | ']'
+                    if prev_cjk || (next_cjk && c != '.') =>
+                {
+                    c = zh_punct(c);
+                }
+                '「' | '『' | '＂' => c = '“',
+                '」' | '』' => c = '”',
+                '"' if prev_cjk || next_cjk => {
+                    c = if double_open { '“' } else { '”' };
+                    double_open = !double_open;
+                }
+                '\'' if prev_cjk || next_cjk => {
+                    c = if single_open { '‘' } else { '’' };
+                    single_open = !single_open;
+                }
+                '〔' | '［' => c = '【',
+                '〕' | '］' => c = '】',
+                _ => {}
+            }
+        }
+
+        // 折叠重复标点
+        let repeated = i > 0 && chars[i - 1] == chars[i] && COLLAPSIBLE.contains(&chars[i]);
+        if repeated || (COLLAPSIBLE.contains(&c) && result.ends_with(c)) {
+            continue;
+        }
+        result.push(c);
+    }
+
+    Ok(result)
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}' | '\u{180E}'
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, nfkc = true, remove_control = true, remove_zero_width = true, remove_soft_hyphen = true))]
+pub fn clean_text(
+    text: &str,
+    nfkc: bool,
+    remove_control: bool,
+    remove_zero_width: bool,
+    remove_soft_hyphen: bool,
+) -> PyResult<String> {
+    let keep = |c: &char| {
+        !((remove_control && c.is_control() && !matches!(c, '\n' | '\t' | '\r'))
+            || (remove_zero_width && is_zero_width(*c))
+            || (remove_soft_hyphen && *c == '\u{00AD}'))
+    };
+    // 先过滤再规范化, 避免不可见字符影响组合
+    let result = if nfkc {
+        text.chars().filter(keep).nfkc().collect()
+    } else {
+        text.chars().filter(keep).collect()
+    };
+
+    Ok(result)
+}
+
+// 前言等处的罗马数字页码 (1-399), 大小写须统一, 避免 "mix"、"Di" 之类的词被误判
+static ROMAN_PAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:c{0,3}(?:xc|xl|l?x{0,3})(?:ix|iv|v?i{0,3})|C{0,3}(?:XC|XL|L?X{0,3})(?:IX|IV|V?I{0,3}))$")
+        .unwrap()
+});
+
+// 页码行: "12", "- 12 -", "第 12 页", "xiv", "12 / 300"
+fn is_page_number(line: &str) -> bool {
+    let core = line
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '—' | '·' | '•' | '|'))
+        .trim_start_matches('第')
+        .trim_end_matches('页')
+        .trim();
+    if core.is_empty() {
+        return false;
+    }
+    let digits_only = core
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '/' || c.is_whitespace());
+    let roman = ROMAN_PAGE.is_match(core);
+    (digits_only && core.chars().any(|c| c.is_ascii_digit())) || roman
+}
+
+// 数字替换为 '#', 使仅页码不同的页眉页脚视为相同
+fn furniture_key(line: &str) -> String {
+    let mut key = String::new();
+    for c in line.trim().chars() {
+        if c.is_ascii_digit() {
+            if !key.ends_with('#') {
+                key.push('#');
+            }
+        } else if !c.is_whitespace() {
+            key.push(c);
+        }
+    }
+    key
+}
+
+#[pyfunction]
+#[pyo3(signature = (pages, min_ratio = 0.5, window = 3))]
+pub fn strip_page_furniture(
+    pages: Vec<String>,
+    min_ratio: f64,
+    window: usize,
+) -> PyResult<(Vec<String>, Vec<(usize, String)>)> {
+    // 每页首尾 window 个非空行的位置, 正数为自顶部起, 负数为自底部起
+    let candidates: Vec<Vec<(usize, i64)>> = pages
+        .iter()
+        .map(|page| {
+            let lines: Vec<usize> = page
+                .lines()
+                .enumerate()
+                .filter(|(_, l)| !l.trim().is_empty())
+                .map(|(i, _)| i)
+                .collect();
+            let mut result: Vec<(usize, i64)> = Vec::new();
+            for (k, &i) in lines.iter().take(window).enumerate() {
+                result.push((i, k as i64));
+            }
+            for (k, &i) in lines.iter().rev().take(window).enumerate() {
+                if !result.iter().any(|(j, _)| *j == i) {
+                    result.push((i, -(k as i64) - 1));
+                }
+            }
+            result
+        })
+        .collect();
+
+    let mut counts: HashMap<(i64, String), usize> = HashMap::new();
+    for (page, lines) in pages.iter().zip(&candidates) {
+        let page_lines: Vec<&str> = page.lines().collect();
+        for &(i, pos) in lines {
+            *counts
+                .entry((pos, furniture_key(page_lines[i])))
+                .or_default() += 1;
+        }
+    }
+    let threshold = ((pages.len() as f64 * min_ratio).ceil() as usize).max(2);
+
+    let mut cleaned = Vec::with_capacity(pages.len());
+    let mut removed = Vec::new();
+    for (p, (page, lines)) in pages.iter().zip(&candidates).enumerate() {
+        let page_lines: Vec<&str> = page.lines().collect();
+        let mut drop = vec![false; page_lines.len()];
+        for &(i, pos) in lines {
+            let line = page_lines[i];
+            let repeated = counts[&(pos, furniture_key(line))] >= threshold;
+            if repeated || is_page_number(line) {
+                drop[i] = true;
+                removed.push((p, line.trim().to_string()));
+            }
+        }
+        let kept: Vec<&str> = page_lines
+            .iter()
+            .zip(&drop)
+            .filter(|(_, d)| !**d)
+            .map(|(l, _)| *l)
+            .collect();
+        cleaned.push(kept.join("\n").trim().to_string());
+    }
+
+    Ok((cleaned, removed))
+}
+
+// 教材文本中正常出现的字符范围
+fn is_expected(c: char) -> bool {
+    matches!(c,
+        ' '..='~'
+        | '\u{00B7}' | '\u{00D7}' | '\u{00F7}' | '\u{00B0}' | '\u{00B1}'
+        | '\u{0370}'..='\u{03FF}'
+        | '\u{2000}'..='\u{206F}'
+        | '\u{2100}'..='\u{23FF}'
+        | '\u{2460}'..='\u{24FF}'
+        | '\u{2500}'..='\u{27BF}'
+        | '\u{3000}'..='\u{303F}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF01}'..='\u{FF5E}')
+}
+
+#[pyfunction]
+pub fn garbled_score(text: &str) -> PyResult<f64> {
+    let mut total = 0usize;
+    let mut bad = 0usize;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        total += 1;
+        // 替换字符、私用区字符 (字体映射缺失) 与其他意外字符
+        if c == '\u{FFFD}' || ('\u{E000}'..='\u{F8FF}').contains(&c) || !is_expected(c) {
+            bad += 1;
+        }
+    }
+    if total == 0 {
+        return Ok(0.0);
+    }
+    // GBK/UTF-8 错误解码的典型产物
+    for pattern in ["锟斤拷", "烫烫烫", "屯屯屯"] {
+        bad += text.matches(pattern).count() * 3;
+    }
+
+    Ok((bad as f64 / total as f64).min(1.0))
+}
+
+fn superscript_value(c: char) -> Option<u32> {
+    match c {
+        '¹' => Some(1),
+        '²' => Some(2),
+        '³' => Some(3),
+        '⁰' | '⁴'..='⁹' => Some(c as u32 - 0x2070),
+        _ => None,
+    }
+}
+
+// 脚注标记的样式与编号: ①, [1], ¹, 1)
+fn marker_key(marker: &str) -> Option<(char, u32)> {
+    let first = marker.chars().next()?;
+    if ('①'..='⑳').contains(&first) {
+        return Some(('①', first as u32 - '①' as u32 + 1));
+    }
+    if first == '[' {
+        return Some(('[', marker.trim_matches(['[', ']']).parse().ok()?));
+    }
+    if first.is_ascii_digit() {
+        return Some(('1', marker.trim_end_matches([')', '）']).parse().ok()?));
+    }
+    let value = marker
+        .chars()
+        .try_fold(0, |acc, c| Some(acc * 10 + superscript_value(c)?))?;
+    Some(('¹', value))
+}
+
+static FOOTNOTE_MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[①-⑳]|\[\d{1,3}\]|[¹²³⁰⁴-⁹]+").unwrap());
+
+static FOOTNOTE_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*([①-⑳]|\[\d{1,3}\]|[¹²³⁰⁴-⁹]+|\d{1,2}[)）])\s*(.*)$").unwrap());
+
+static FOOTNOTE_SEPARATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*[-_—─―]{3,}\s*$").unwrap());
+
+#[pyfunction]
+#[pyo3(signature = (text, mode = "strip"))]
+pub fn handle_footnotes(text: &str, mode: &str) -> PyResult<(String, Vec<(u32, String)>)> {
+    if mode != "strip" && mode != "inline" {
+        return Err(PyValueError::new_err(format!(
+            "未知的 mode: {}, 可选 strip/inline",
+            mode
+        )));
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    // 自底向上寻找脚注块, 脚注行之间允许少量续行
+    let mut block_start = lines.len();
+    let mut gap = 0;
+    let mut separated = false;
+    for (i, line) in lines.iter().enumerate().rev() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if FOOTNOTE_LINE.is_match(line) {
+            block_start = i;
+            gap = 0;
+        } else if FOOTNOTE_SEPARATOR.is_match(line) {
+            if block_start < lines.len() {
+                block_start = i;
+                separated = true;
+            }
+            break;
+        } else {
+            gap += 1;
+            if gap > 2 {
+                break;
+            }
+        }
+    }
+
+    let mut notes: Vec<((char, u32), String)> = Vec::new();
+    for line in &lines[block_start..] {
+        if FOOTNOTE_SEPARATOR.is_match(line) || line.trim().is_empty() {
+            continue;
+        }
+        match FOOTNOTE_LINE.captures(line) {
+            Some(caps) => {
+                if let Some(key) = marker_key(&caps[1]) {
+                    notes.push((key, caps[2].trim().to_string()));
+                }
+            }
+            // 续行并入上一条脚注
+            None => {
+                if let Some((_, note)) = notes.last_mut() {
+                    note.push_str(line.trim());
+                }
+            }
+        }
+    }
+    if notes.is_empty() {
+        return Ok((text.to_string(), Vec::new()));
+    }
+
+    let body = lines[..block_start].join("\n");
+    // 正文中的 "1)" 不视为标记, 对应的脚注按数字匹配 "¹"
+    let find_note = |marker: &str| {
+        let key = marker_key(marker);
+        notes.iter().find(|(k, _)| {
+            Some(*k) == key || (k.0 == '1' && key.is_some_and(|(s, v)| s == '¹' && v == k.1))
+        })
+    };
+    // 没有分隔线时, 正文中须引用了其中的脚注, 否则末尾的 ① / [1] 列表只是普通列举
+    if !separated
+        && !FOOTNOTE_MARKER
+            .find_iter(&body)
+            .any(|m| find_note(m.as_str()).is_some())
+    {
+        return Ok((text.to_string(), Vec::new()));
+    }
+    let body = FOOTNOTE_MARKER.replace_all(&body, |caps: &regex::Captures| {
+        let marker = &caps[0];
+        match find_note(marker) {
+            Some((_, note)) if mode == "inline" => format!("（{}）", note),
+            Some(_) => String::new(),
+            None => marker.to_string(),
+        }
+    });
+
+    let notes = notes.into_iter().map(|((_, n), note)| (n, note)).collect();
+    Ok((body.trim_end().to_string(), notes))
+}
+
+pub fn warm_up() {
+    Lazy::force(&FOOTNOTE_MARKER);
+    Lazy::force(&FOOTNOTE_LINE);
+    Lazy::force(&FOOTNOTE_SEPARATOR);
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(join_pdf_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_cjk_spacing, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_punct, m)?)?;
+    m.add_function(wrap_pyfunction!(clean_text, m)?)?;
+    m.add_function(wrap_pyfunction!(strip_page_furniture, m)?)?;
+    m.add_function(wrap_pyfunction!(garbled_score, m)?)?;
+    m.add_function(wrap_pyfunction!(handle_footnotes, m)?)?;
+    Ok(())
+}
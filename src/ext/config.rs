@@ -0,0 +1,157 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::errors::{ChunkingError, LayoutError};
+use super::length::{grapheme_ceil, grapheme_floor};
+
+// 各处理函数的默认参数, 函数显式传入的参数优先
+#[pyclass(get_all, set_all)]
+#[derive(Clone)]
+pub struct CoreConfig {
+    pub chunk_size: i32,
+    pub overlap: usize,
+    pub delimiters: Vec<String>,
+    pub iou_threshold: f32,
+    pub similarity_threshold: f64,
+    pub dedup_threshold: f64,
+    pub grapheme_safe: bool,
+    pub stable: bool,
+}
+
+impl Default for CoreConfig {
+    fn default() -> Self {
+        CoreConfig {
+            chunk_size: 400,
+            overlap: 0,
+            delimiters: vec!["。".to_string()],
+            iou_threshold: 0.1,
+            similarity_threshold: 0.85,
+            dedup_threshold: 0.8,
+            grapheme_safe: true,
+            stable: false,
+        }
+    }
+}
+
+#[pymethods]
+impl CoreConfig {
+    #[new]
+    #[pyo3(signature = (chunk_size=400, overlap=0, delimiters=None, iou_threshold=0.1, similarity_threshold=0.85, dedup_threshold=0.8, grapheme_safe=true, stable=false))]
+    // 参数与 Python 端的关键字参数一一对应
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        chunk_size: i32,
+        overlap: usize,
+        delimiters: Option<Vec<String>>,
+        iou_threshold: f32,
+        similarity_threshold: f64,
+        dedup_threshold: f64,
+        grapheme_safe: bool,
+        stable: bool,
+    ) -> PyResult<Self> {
+        if chunk_size <= 0 {
+            return Err(ChunkingError::new_err(format!(
+                "分块长度必须大于 0, 实际为 {}",
+                chunk_size
+            )));
+        }
+        if overlap >= chunk_size as usize {
+            return Err(ChunkingError::new_err(format!(
+                "overlap ({}) 必须小于分块长度 ({})",
+                overlap, chunk_size
+            )));
+        }
+        let delimiters = delimiters.unwrap_or_else(|| vec!["。".to_string()]);
+        if delimiters.is_empty() || delimiters.iter().any(String::is_empty) {
+            return Err(ChunkingError::new_err(
+                "delimiters 不能为空, 也不能包含空字符串",
+            ));
+        }
+        if !(0.0..=1.0).contains(&iou_threshold) {
+            return Err(LayoutError::new_err(format!(
+                "iou_threshold 应在 [0, 1] 内, 实际为 {}",
+                iou_threshold
+            )));
+        }
+        for (name, value) in [
+            ("similarity_threshold", similarity_threshold),
+            ("dedup_threshold", dedup_threshold),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(PyValueError::new_err(format!(
+                    "{} 应在 [0, 1] 内, 实际为 {}",
+                    name, value
+                )));
+            }
+        }
+        Ok(CoreConfig {
+            chunk_size,
+            overlap,
+            delimiters,
+            iou_threshold,
+            similarity_threshold,
+            dedup_threshold,
+            grapheme_safe,
+            stable,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CoreConfig(chunk_size={}, overlap={}, delimiters=[{}], iou_threshold={}, similarity_threshold={}, dedup_threshold={}, grapheme_safe={}, stable={})",
+            self.chunk_size,
+            self.overlap,
+            self.delimiters
+                .iter()
+                .map(|d| format!("'{}'", d))
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.iou_threshold,
+            self.similarity_threshold,
+            self.dedup_threshold,
+            if self.grapheme_safe { "True" } else { "False" },
+            if self.stable { "True" } else { "False" }
+        )
+    }
+}
+
+// 按任一分隔符切分, 返回 (片段, 其后的分隔符), 末尾片段的分隔符为空;
+// grapheme_safe 时分隔符后紧跟的组合字符等并入分隔符, 切分点总在字素簇边界上
+pub fn split_sentences<'a>(
+    text: &'a str,
+    delimiters: &[String],
+    grapheme_safe: bool,
+) -> Vec<(&'a str, &'a str)> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        match delimiters
+            .iter()
+            .find(|d| !d.is_empty() && text[pos..].starts_with(d.as_str()))
+        {
+            Some(d) if grapheme_safe && grapheme_floor(text, pos) != pos => {
+                pos += d.len();
+            }
+            Some(d) => {
+                let mut end = pos + d.len();
+                if grapheme_safe {
+                    end = grapheme_ceil(text, end);
+                }
+                result.push((&text[start..pos], &text[pos..end]));
+                pos = end;
+                start = pos;
+            }
+            None => pos += text[pos..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if start < text.len() {
+        result.push((&text[start..], ""));
+    }
+    result
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<CoreConfig>()?;
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use super::config::CoreConfig;
+use super::threads::install;
+
+// 梅森素数 2^61 - 1, 用作置换哈希的模数
+const MERSENNE_PRIME: u64 = (1 << 61) - 1;
+
+// FNV-1a, 保证跨进程与跨版本结果一致
+pub fn fnv1a(chars: &[char]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &c in chars {
+        for byte in (c as u32).to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+// 去除空白后的字符 shingle 哈希, 短于 size 的非空串整体作为一个 shingle
+fn shingle_hashes(text: &str, size: usize) -> Vec<u64> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.len() < size {
+        return chars.chunks(size).map(fnv1a).collect();
+    }
+    chars.windows(size).map(fnv1a).collect()
+}
+
+// 并查集查找, 带路径压缩
+pub fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+#[pyclass]
+pub struct MinHasher {
+    shingle_size: usize,
+    bands: usize,
+    rows: usize,
+    // 每个置换的 (a, b) 参数
+    permutations: Vec<(u64, u64)>,
+}
+
+impl MinHasher {
+    fn compute(&self, text: &str) -> Vec<u64> {
+        let shingles: HashSet<u64> = shingle_hashes(text, self.shingle_size)
+            .into_iter()
+            .collect();
+        self.permutations
+            .iter()
+            .map(|&(a, b)| {
+                shingles
+                    .iter()
+                    .map(|&h| {
+                        let h = (h % MERSENNE_PRIME) as u128;
+                        ((a as u128 * h + b as u128) % MERSENNE_PRIME as u128) as u64
+                    })
+                    .min()
+                    .unwrap_or(u64::MAX)
+            })
+            .collect()
+    }
+}
+
+#[pymethods]
+impl MinHasher {
+    #[new]
+    #[pyo3(signature = (shingle_size = 5, num_perm = 128, bands = 16, seed = 1))]
+    fn new(shingle_size: usize, num_perm: usize, bands: usize, seed: u64) -> PyResult<Self> {
+        if shingle_size == 0 || num_perm == 0 || bands == 0 {
+            return Err(PyValueError::new_err(
+                "shingle_size, num_perm 与 bands 必须大于 0",
+            ));
+        }
+        if !num_perm.is_multiple_of(bands) {
+            return Err(PyValueError::new_err(format!(
+                "num_perm ({}) 必须能被 bands ({}) 整除",
+                num_perm, bands
+            )));
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let permutations = (0..num_perm)
+            .map(|_| {
+                (
+                    rng.gen_range(1..MERSENNE_PRIME),
+                    rng.gen_range(0..MERSENNE_PRIME),
+                )
+            })
+            .collect();
+        Ok(MinHasher {
+            shingle_size,
+            bands,
+            rows: num_perm / bands,
+            permutations,
+        })
+    }
+
+    fn signature(&self, text: &str) -> PyResult<Vec<u64>> {
+        Ok(self.compute(text))
+    }
+
+    #[pyo3(signature = (chunks, threshold = None, config = None))]
+    fn dedup(
+        &self,
+        py: Python<'_>,
+        chunks: Vec<String>,
+        threshold: Option<f64>,
+        config: Option<CoreConfig>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        let threshold = threshold.unwrap_or_else(|| config.unwrap_or_default().dedup_threshold);
+        py.allow_threads(|| {
+            let signatures: Vec<Vec<u64>> =
+                install(|| chunks.par_iter().map(|c| self.compute(c)).collect());
+
+            // 任一条带完全相同的块互为候选
+            let mut parent: Vec<usize> = (0..chunks.len()).collect();
+            for band in 0..self.bands {
+                let range = band * self.rows..(band + 1) * self.rows;
+                let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+                for (i, sig) in signatures.iter().enumerate() {
+                    buckets.entry(&sig[range.clone()]).or_default().push(i);
+                }
+                for bucket in buckets.values().filter(|b| b.len() > 1) {
+                    // 逐对比较桶内的块, 与首个块不相似的块之间也可能相似
+                    for (k, &other) in bucket.iter().enumerate().skip(1) {
+                        for &earlier in &bucket[..k] {
+                            let (ra, rb) = (
+                                find_root(&mut parent, earlier),
+                                find_root(&mut parent, other),
+                            );
+                            if ra == rb {
+                                continue;
+                            }
+                            // 以签名估计的 Jaccard 相似度确认候选
+                            let same = signatures[earlier]
+                                .iter()
+                                .zip(&signatures[other])
+                                .filter(|(x, y)| x == y)
+                                .count();
+                            if same as f64 / self.permutations.len() as f64 >= threshold {
+                                parent[ra.max(rb)] = ra.min(rb);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+            for i in 0..chunks.len() {
+                let root = find_root(&mut parent, i);
+                groups.entry(root).or_default().push(i);
+            }
+            let mut result: Vec<Vec<usize>> =
+                groups.into_values().filter(|g| g.len() > 1).collect();
+            result.sort();
+
+            Ok(result)
+        })
+    }
+}
+
+// 以字符 shingle 为特征、出现次数为权重的 64 位 SimHash
+pub fn simhash_of(text: &str, shingle_size: usize) -> u64 {
+    let mut weights: HashMap<u64, i64> = HashMap::new();
+    for hash in shingle_hashes(text, shingle_size) {
+        *weights.entry(hash).or_default() += 1;
+    }
+
+    let mut vector = [0i64; 64];
+    for (hash, weight) in weights {
+        for (bit, v) in vector.iter_mut().enumerate() {
+            if hash >> bit & 1 == 1 {
+                *v += weight;
+            } else {
+                *v -= weight;
+            }
+        }
+    }
+    vector
+        .iter()
+        .enumerate()
+        .filter(|(_, &v)| v > 0)
+        .fold(0, |acc, (bit, _)| acc | 1 << bit)
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, shingle_size=3))]
+pub fn simhash(text: &str, shingle_size: usize) -> PyResult<u64> {
+    if shingle_size == 0 {
+        return Err(PyValueError::new_err("shingle_size 必须大于 0"));
+    }
+    Ok(simhash_of(text, shingle_size))
+}
+
+#[pyfunction]
+pub fn hamming_distance(a: u64, b: u64) -> PyResult<u32> {
+    Ok((a ^ b).count_ones())
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<MinHasher>()?;
+    m.add_function(wrap_pyfunction!(simhash, m)?)?;
+    m.add_function(wrap_pyfunction!(hamming_distance, m)?)?;
+    Ok(())
+}
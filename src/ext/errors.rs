@@ -0,0 +1,38 @@
+// pyo3 0.22 的 create_exception! 展开中引用了本 crate 未声明的 gil-refs 特性
+#![allow(unexpected_cfgs)]
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// 扩展模块异常的基类, 继承 ValueError, 原先捕获 ValueError 的调用方不受影响
+create_exception!(_core, CourseKgError, PyValueError);
+// 文本分块参数或输入不合法
+create_exception!(_core, ChunkingError, CourseKgError);
+// 大模型输出等文本解析失败
+create_exception!(_core, ParseError, CourseKgError);
+// 版面检测结果 (检测框) 不合法
+create_exception!(_core, LayoutError, CourseKgError);
+
+// 解析失败, position 为出错位置的字符偏移 (未知时为 None), reason 为原因
+pub fn parse_error(py: Python<'_>, position: Option<usize>, reason: &str) -> PyErr {
+    let message = match position {
+        Some(position) => format!("{} (位置 {})", reason, position),
+        None => reason.to_string(),
+    };
+    let err = ParseError::new_err(message);
+    let value = err.value_bound(py);
+    // 设置属性失败时仍返回原异常
+    let _ = value.setattr("position", position);
+    let _ = value.setattr("reason", reason);
+    err
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("CourseKgError", py.get_type_bound::<CourseKgError>())?;
+    m.add("ChunkingError", py.get_type_bound::<ChunkingError>())?;
+    m.add("ParseError", py.get_type_bound::<ParseError>())?;
+    m.add("LayoutError", py.get_type_bound::<LayoutError>())?;
+    Ok(())
+}
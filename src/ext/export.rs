@@ -0,0 +1,463 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::{json, Map, Value};
+
+use super::json::py_to_json;
+
+// 反引号包裹的标识符, 用于标签与关系类型
+pub fn cypher_identifier(s: &str) -> String {
+    format!("`{}`", s.replace('`', "``"))
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, node_label="Concept", batch_size=1000))]
+pub fn to_cypher<'py>(
+    py: Python<'py>,
+    triples: Vec<(String, String, String)>,
+    node_label: &str,
+    batch_size: usize,
+) -> PyResult<Vec<(String, Vec<Bound<'py, PyDict>>)>> {
+    if batch_size == 0 {
+        return Err(PyValueError::new_err("batch_size 必须大于 0"));
+    }
+    if node_label.is_empty() {
+        return Err(PyValueError::new_err("node_label 不能为空"));
+    }
+    let label = cypher_identifier(node_label);
+
+    // 关系类型不能参数化, 按关系分组生成语句
+    let mut order: Vec<&str> = Vec::new();
+    let mut groups: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for (head, relation, tail) in &triples {
+        groups
+            .entry(relation)
+            .or_insert_with(|| {
+                order.push(relation);
+                Vec::new()
+            })
+            .push((head, tail));
+    }
+
+    // 实体名通过 $rows 参数传入, 同一关系的语句文本相同, 可复用查询计划
+    let mut statements = Vec::new();
+    for relation in order {
+        let statement = format!(
+            "UNWIND $rows AS row\nMERGE (h:{label} {{name: row.head}})\nMERGE (t:{label} {{name: row.tail}})\nMERGE (h)-[:{}]->(t)",
+            cypher_identifier(relation),
+        );
+        for batch in groups[relation].chunks(batch_size) {
+            let rows = batch
+                .iter()
+                .map(|(head, tail)| {
+                    let row = PyDict::new_bound(py);
+                    row.set_item("head", head)?;
+                    row.set_item("tail", tail)?;
+                    Ok(row)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            statements.push((statement.clone(), rows));
+        }
+    }
+
+    Ok(statements)
+}
+
+// CSV 字段, 含分隔符、引号或换行时加引号
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> PyResult<()> {
+    fs::write(path, content)
+        .map_err(|e| PyValueError::new_err(format!("无法写入 {}: {}", path.display(), e)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, out_dir, node_label="Concept"))]
+pub fn to_neo4j_csv(
+    triples: Vec<(String, String, String)>,
+    out_dir: &str,
+    node_label: &str,
+) -> PyResult<(usize, usize)> {
+    let dir = Path::new(out_dir);
+    fs::create_dir_all(dir)
+        .map_err(|e| PyValueError::new_err(format!("无法创建目录 {}: {}", out_dir, e)))?;
+
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut nodes = String::from("id:ID,name,:LABEL\n");
+    let mut seen: HashSet<(usize, &str, usize)> = HashSet::new();
+    let mut relationships = String::from(":START_ID,:END_ID,:TYPE\n");
+    for (head, relation, tail) in &triples {
+        let [h, t] = [head, tail].map(|name| {
+            let next = ids.len();
+            *ids.entry(name).or_insert_with(|| {
+                nodes.push_str(&format!(
+                    "n{},{},{}\n",
+                    next,
+                    csv_field(name),
+                    csv_field(node_label)
+                ));
+                next
+            })
+        });
+        if seen.insert((h, relation, t)) {
+            relationships.push_str(&format!("n{},n{},{}\n", h, t, csv_field(relation)));
+        }
+    }
+
+    write_file(&dir.join("nodes.csv"), &nodes)?;
+    write_file(&dir.join("relationships.csv"), &relationships)?;
+
+    Ok((ids.len(), seen.len()))
+}
+
+// IRI 路径段编码: 保留 Unicode 字符, 转义 IRI 中不允许或有特殊含义的字符
+pub fn iri_segment(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        let unsafe_char = c.is_control()
+            || c.is_whitespace()
+            || matches!(
+                c,
+                '<' | '>'
+                    | '"'
+                    | '{'
+                    | '}'
+                    | '|'
+                    | '^'
+                    | '`'
+                    | '\\'
+                    | '/'
+                    | '#'
+                    | '?'
+                    | '%'
+                    | '['
+                    | ']'
+                    | '@'
+                    | ':'
+            );
+        if unsafe_char {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                result.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// N-Triples / Turtle 字符串字面量
+fn rdf_literal(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+
+#[pyfunction]
+#[pyo3(signature = (triples, base_iri, format="ntriples", predicate_map=None, lang="zh"))]
+pub fn to_rdf(
+    triples: Vec<(String, String, String)>,
+    base_iri: &str,
+    format: &str,
+    predicate_map: Option<HashMap<String, String>>,
+    lang: &str,
+) -> PyResult<String> {
+    let turtle = match format {
+        "ntriples" | "nt" => false,
+        "turtle" | "ttl" => true,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "未知的 RDF 格式: {}",
+                format
+            )))
+        }
+    };
+    let predicate_map = predicate_map.unwrap_or_default();
+    let entity = |name: &str| format!("<{}{}>", base_iri, iri_segment(name));
+    let predicate = |relation: &str| match predicate_map.get(relation) {
+        Some(iri) => format!("<{}>", iri),
+        None => format!("<{}relation/{}>", base_iri, iri_segment(relation)),
+    };
+    let label = |name: &str| {
+        if lang.is_empty() {
+            rdf_literal(name)
+        } else {
+            format!("{}@{}", rdf_literal(name), lang)
+        }
+    };
+
+    // 每个主语的 (谓语, 宾语) 列表, 按首次出现的顺序, 首项为 rdfs:label
+    let mut order: Vec<&str> = Vec::new();
+    let mut statements: HashMap<&str, Vec<(String, String)>> = HashMap::new();
+    for (head, relation, tail) in &triples {
+        for name in [head.as_str(), tail.as_str()] {
+            statements.entry(name).or_insert_with(|| {
+                order.push(name);
+                vec![(format!("<{}>", RDFS_LABEL), label(name))]
+            });
+        }
+        let pair = (predicate(relation), entity(tail));
+        let list = statements.get_mut(head.as_str()).unwrap();
+        if !list.contains(&pair) {
+            list.push(pair);
+        }
+    }
+
+    let mut output = String::new();
+    for subject in order {
+        let pairs = &statements[subject];
+        if turtle {
+            let body: Vec<String> = pairs.iter().map(|(p, o)| format!("{} {}", p, o)).collect();
+            output.push_str(&format!(
+                "{}\n    {} .\n",
+                entity(subject),
+                body.join(" ;\n    ")
+            ));
+        } else {
+            for (p, o) in pairs {
+                output.push_str(&format!("{} {} {} .\n", entity(subject), p, o));
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+pub fn xml_escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            // XML 1.0 不允许的控制字符直接丢弃
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, node_attrs=None, format="graphml"))]
+pub fn to_graphml(
+    triples: Vec<(String, String, String)>,
+    node_attrs: Option<HashMap<String, HashMap<String, String>>>,
+    format: &str,
+) -> PyResult<String> {
+    if format != "graphml" && format != "gexf" {
+        return Err(PyValueError::new_err(format!("未知的图格式: {}", format)));
+    }
+    let node_attrs = node_attrs.unwrap_or_default();
+
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut nodes: Vec<&str> = Vec::new();
+    for (head, _, tail) in &triples {
+        for name in [head.as_str(), tail.as_str()] {
+            ids.entry(name).or_insert_with(|| {
+                nodes.push(name);
+                nodes.len() - 1
+            });
+        }
+    }
+    // 属性名排序以保证输出稳定
+    let mut attr_names: Vec<&str> = node_attrs
+        .values()
+        .flat_map(|attrs| attrs.keys().map(String::as_str))
+        .collect();
+    attr_names.sort_unstable();
+    attr_names.dedup();
+    // 每个节点的 (属性序号, 转义后的值)
+    let values: Vec<Vec<(usize, String)>> = nodes
+        .iter()
+        .map(|name| {
+            let Some(attrs) = node_attrs.get(*name) else {
+                return Vec::new();
+            };
+            attr_names
+                .iter()
+                .enumerate()
+                .filter_map(|(k, attr)| attrs.get(*attr).map(|v| (k, xml_escape(v))))
+                .collect()
+        })
+        .collect();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    if format == "graphml" {
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        out.push_str(
+            "  <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n",
+        );
+        for (k, attr) in attr_names.iter().enumerate() {
+            out.push_str(&format!(
+                "  <key id=\"a{}\" for=\"node\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                k,
+                xml_escape(attr)
+            ));
+        }
+        out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        for (i, name) in nodes.iter().enumerate() {
+            out.push_str(&format!(
+                "    <node id=\"n{}\">\n      <data key=\"label\">{}</data>\n",
+                i,
+                xml_escape(name)
+            ));
+            for (k, value) in &values[i] {
+                out.push_str(&format!("      <data key=\"a{}\">{}</data>\n", k, value));
+            }
+            out.push_str("    </node>\n");
+        }
+        for (i, (head, relation, tail)) in triples.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">\n      <data key=\"relation\">{}</data>\n    </edge>\n",
+                i,
+                ids[head.as_str()],
+                ids[tail.as_str()],
+                xml_escape(relation)
+            ));
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+    } else {
+        out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        out.push_str("  <graph mode=\"static\" defaultedgetype=\"directed\">\n");
+        if !attr_names.is_empty() {
+            out.push_str("    <attributes class=\"node\">\n");
+            for (k, attr) in attr_names.iter().enumerate() {
+                out.push_str(&format!(
+                    "      <attribute id=\"a{}\" title=\"{}\" type=\"string\"/>\n",
+                    k,
+                    xml_escape(attr)
+                ));
+            }
+            out.push_str("    </attributes>\n");
+        }
+        out.push_str("    <nodes>\n");
+        for (i, name) in nodes.iter().enumerate() {
+            let attvalues: Vec<String> = values[i]
+                .iter()
+                .map(|(k, v)| format!("          <attvalue for=\"a{}\" value=\"{}\"/>\n", k, v))
+                .collect();
+            if attvalues.is_empty() {
+                out.push_str(&format!(
+                    "      <node id=\"n{}\" label=\"{}\"/>\n",
+                    i,
+                    xml_escape(name)
+                ));
+            } else {
+                out.push_str(&format!(
+                    "      <node id=\"n{}\" label=\"{}\">\n        <attvalues>\n{}        </attvalues>\n      </node>\n",
+                    i,
+                    xml_escape(name),
+                    attvalues.concat()
+                ));
+            }
+        }
+        out.push_str("    </nodes>\n    <edges>\n");
+        for (i, (head, relation, tail)) in triples.iter().enumerate() {
+            out.push_str(&format!(
+                "      <edge id=\"e{}\" source=\"n{}\" target=\"n{}\" label=\"{}\"/>\n",
+                i,
+                ids[head.as_str()],
+                ids[tail.as_str()],
+                xml_escape(relation)
+            ));
+        }
+        out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+    }
+
+    Ok(out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, context=None, base_iri="urn:coursekg:", pretty=false))]
+pub fn to_jsonld(
+    triples: Vec<(String, String, String)>,
+    context: Option<&Bound<'_, PyAny>>,
+    base_iri: &str,
+    pretty: bool,
+) -> PyResult<String> {
+    // 关系属性名统一加 rel: 前缀, 避免名为 name 或 @id 等的关系与节点自身的键冲突
+    let prefix = json!({
+        "rel": format!("{}relation/", base_iri),
+        "name": "http://www.w3.org/2000/01/rdf-schema#label",
+    });
+    let context = match context {
+        // 自定义上下文放在后面, 可以覆盖 rel 前缀与 name 映射
+        Some(context) => json!([prefix, py_to_json(context)?]),
+        None => prefix,
+    };
+    let id = |name: &str| format!("{}entity/{}", base_iri, iri_segment(name));
+
+    // 每个实体一个节点对象, 关系作为属性, 取值为所指向实体的 @id 列表
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut graph: Vec<Map<String, Value>> = Vec::new();
+    for (head, relation, tail) in &triples {
+        for name in [head.as_str(), tail.as_str()] {
+            index.entry(name).or_insert_with(|| {
+                let mut node = Map::new();
+                node.insert("@id".to_string(), Value::String(id(name)));
+                node.insert("name".to_string(), Value::String(name.to_string()));
+                graph.push(node);
+                graph.len() - 1
+            });
+        }
+        let target = json!({ "@id": id(tail) });
+        let values = graph[index[head.as_str()]]
+            .entry(format!("rel:{}", iri_segment(relation)))
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(values) = values {
+            if !values.contains(&target) {
+                values.push(target);
+            }
+        }
+    }
+
+    let document = json!({
+        "@context": context,
+        "@graph": graph,
+    });
+    let text = if pretty {
+        serde_json::to_string_pretty(&document)
+    } else {
+        serde_json::to_string(&document)
+    };
+    text.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(to_cypher, m)?)?;
+    m.add_function(wrap_pyfunction!(to_neo4j_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(to_rdf, m)?)?;
+    m.add_function(wrap_pyfunction!(to_graphml, m)?)?;
+    m.add_function(wrap_pyfunction!(to_jsonld, m)?)?;
+    Ok(())
+}
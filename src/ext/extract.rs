@@ -0,0 +1,237 @@
+use log::debug;
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use regex::Regex;
+
+use super::clean::is_cjk;
+use super::heading::parse_cn_heading;
+
+// 按行切分并记录每行的字符偏移
+fn lines_with_offsets(text: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        result.push((offset, line.trim_end_matches(['\n', '\r'])));
+        offset += line.chars().count();
+    }
+    result
+}
+
+static REF_HEADING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^\s*(?:第.{1,3}章\s*)?(?:主要)?(?:参考文献|参考资料|参考书目|references|bibliography)\s*[:：]?\s*$",
+    ).unwrap()
+});
+
+static REF_NEXT_SECTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*(?:附\s*录|索\s*引|后\s*记|致\s*谢|appendix|index|acknowledg)").unwrap()
+});
+
+static REF_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:\[\d{1,4}\]|［\d{1,4}］|\d{1,4}[.．、]|\(\d{1,4}\))\s*\S").unwrap()
+});
+
+// 文献类型标识 [M] [J] [EB/OL] 等, 或年份加出版信息
+static REF_CITATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\[(?:M|J|C|D|R|S|P|N|G|Z|A|EB/OL|DB/OL|CP/DK)\]|出版社|et al\.|\b(?:19|20)\d{2}[a-z]?\s*[,，.:：;；)]",
+    ).unwrap()
+});
+
+#[pyfunction]
+pub fn detect_references_section(
+    text: &str,
+) -> PyResult<(Option<(usize, usize)>, Vec<(usize, usize, String)>)> {
+    let lines = lines_with_offsets(text);
+    let total = text.chars().count();
+    let mut section = None;
+    let mut entries: Vec<(usize, usize, String)> = Vec::new();
+    let mut in_section = false;
+
+    for (offset, line) in &lines {
+        let end = offset + line.chars().count();
+        if REF_HEADING.is_match(line) {
+            in_section = true;
+            section = Some((*offset, total));
+            continue;
+        }
+        if in_section {
+            let is_chapter =
+                matches!(parse_cn_heading(line), Ok(Some((level, _, _))) if level <= 1);
+            if REF_NEXT_SECTION.is_match(line) || (is_chapter && !REF_ENTRY.is_match(line)) {
+                debug!("参考文献部分在偏移 {} 处结束: {}", offset, line.trim());
+                in_section = false;
+                if let Some((start, _)) = section {
+                    section = Some((start, *offset));
+                }
+                continue;
+            }
+        }
+
+        if REF_ENTRY.is_match(line) && (in_section || REF_CITATION.is_match(line)) {
+            entries.push((*offset, end, line.trim().to_string()));
+        } else if in_section && !line.trim().is_empty() {
+            // 参考文献条目的续行
+            if let Some(last) = entries.last_mut() {
+                if last.1 + 1 == *offset || last.1 == *offset {
+                    let line = line.trim();
+                    let joins_latin = !last.2.ends_with(is_cjk) && !line.starts_with(is_cjk);
+                    if joins_latin {
+                        last.2.push(' ');
+                    }
+                    last.1 = end;
+                    last.2.push_str(line);
+                }
+            }
+        }
+    }
+
+    Ok((section, entries))
+}
+
+static EXERCISE_SECTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:第.{1,3}[章节]\s*)?(课后习题|习题|思考题|练习题|练习|复习题|作业题|作业)[\s\d.．一二三四五六七八九十]*[:：]?\s*$",
+    ).unwrap()
+});
+
+static EXERCISE_QUESTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(\d{1,3}(?:[.．]\d{1,3})*)\s*[.．、)）]\s*\S").unwrap());
+
+static EXERCISE_EXAMPLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*[【\[]?例\s*(\d{1,3}(?:[.．-]\d{1,3})*)\s*[】\]]?\s*[:：.．]?").unwrap()
+});
+
+#[pyfunction]
+pub fn extract_exercises(text: &str) -> PyResult<Vec<(String, String, String, usize, usize)>> {
+    // 类别, 编号, 文本, 起始偏移, 结束偏移
+    let mut result: Vec<(String, String, String, usize, usize)> = Vec::new();
+    let mut current: Option<usize> = None;
+    let mut in_section: Option<String> = None;
+
+    for (offset, line) in lines_with_offsets(text) {
+        let end = offset + line.chars().count();
+        if let Some(caps) = EXERCISE_SECTION.captures(line) {
+            in_section = Some(caps[1].to_string());
+            current = None;
+            continue;
+        }
+        let is_heading = line.trim_start().starts_with('#')
+            || matches!(parse_cn_heading(line), Ok(Some((level, _, _))) if level <= 2);
+        if is_heading {
+            in_section = None;
+            current = None;
+            continue;
+        }
+
+        let started = if let Some(caps) = EXERCISE_EXAMPLE.captures(line) {
+            Some(("例题".to_string(), caps[1].replace('．', ".")))
+        } else if let (Some(kind), Some(caps)) = (&in_section, EXERCISE_QUESTION.captures(line)) {
+            Some((kind.clone(), caps[1].replace('．', ".")))
+        } else {
+            None
+        };
+
+        match started {
+            Some((kind, number)) => {
+                result.push((kind, number, line.trim().to_string(), offset, end));
+                current = Some(result.len() - 1);
+            }
+            None => {
+                if let Some(i) = current {
+                    if !line.trim().is_empty() {
+                        let item = &mut result[i];
+                        item.2.push('\n');
+                        item.2.push_str(line.trim());
+                        item.4 = end;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+static DEFINITION_SENTENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[^。！？!?\n]+[。！？!?]?").unwrap());
+
+const TERM: &str = r"[^，。,：:；;、！？!?\s（）()“”\x22]{1,20}?";
+
+static DEFINITION_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        // X是指… / X（英文）被定义为…
+        format!(
+            r"(?:^|[，,；;])\s*(?:所谓)?(?P<t>{TERM})\s*(?:（[^）]*）|\([^)]*\))?\s*(?:是指|指的是|被定义为|定义为|就是指)"
+        ),
+        // 所谓X，…
+        format!(r"(?:^|[，,；;])\s*所谓(?P<t>{TERM})\s*(?:（[^）]*）|\([^)]*\))?\s*[，,]"),
+        // X，也称Y，…
+        format!(
+            r"(?:^|[，,；;])\s*(?P<t>{TERM})\s*(?:（[^）]*）|\([^)]*\))?\s*[，,]\s*(?:也称|又称|亦称|也叫|又叫|或称|简称)(?:为)?(?P<a>{TERM})\s*(?:[，,。]|$)"
+        ),
+        // 把…称为X
+        format!(
+            r"(?:把|将)[^，。]{{1,40}}(?:称为|称作|叫做|定义为)(?P<t>{TERM})\s*(?:[，,。；;]|$)"
+        ),
+        // X是一种…
+        format!(r"^\s*(?P<t>{TERM})\s*(?:（[^）]*）|\([^)]*\))?\s*是(?:一种|一类|一组|一个)"),
+    ]
+    .iter()
+    .map(|p| Regex::new(p).unwrap())
+    .collect()
+});
+
+#[pyfunction]
+pub fn extract_definitions(text: &str) -> PyResult<Vec<(String, String)>> {
+    const PRONOUNS: [&str; 9] = ["这", "那", "它", "其", "该", "此", "他", "我们", "本"];
+
+    let mut result: Vec<(String, String)> = Vec::new();
+    for m in DEFINITION_SENTENCE.find_iter(text) {
+        let s = m.as_str().trim();
+        for pattern in DEFINITION_PATTERNS.iter() {
+            let Some(caps) = pattern.captures(s) else {
+                continue;
+            };
+            for name in ["t", "a"] {
+                let Some(term) = caps.name(name) else {
+                    continue;
+                };
+                let term = term.as_str().trim();
+                if term.is_empty() {
+                    continue;
+                }
+                if PRONOUNS.iter().any(|p| term.starts_with(p)) {
+                    debug!("跳过以代词开头的术语: {:?}", term);
+                    continue;
+                }
+                let item = (term.to_string(), s.to_string());
+                if !result.contains(&item) {
+                    result.push(item);
+                }
+            }
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn warm_up() {
+    Lazy::force(&REF_HEADING);
+    Lazy::force(&REF_NEXT_SECTION);
+    Lazy::force(&REF_ENTRY);
+    Lazy::force(&REF_CITATION);
+    Lazy::force(&EXERCISE_SECTION);
+    Lazy::force(&EXERCISE_QUESTION);
+    Lazy::force(&EXERCISE_EXAMPLE);
+    Lazy::force(&DEFINITION_SENTENCE);
+    Lazy::force(&DEFINITION_PATTERNS);
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(detect_references_section, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_exercises, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_definitions, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,921 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::dedup::find_root;
+use super::kg::check_direction;
+
+// 由边列表构建的有向图, 节点按首次出现的顺序编号, 重复边只保留一条
+pub struct Digraph {
+    pub names: Vec<String>,
+    pub index: HashMap<String, usize>,
+    pub out: Vec<Vec<usize>>,
+    pub inc: Vec<Vec<usize>>,
+}
+
+impl Digraph {
+    pub fn new() -> Self {
+        Digraph {
+            names: Vec::new(),
+            index: HashMap::new(),
+            out: Vec::new(),
+            inc: Vec::new(),
+        }
+    }
+
+    pub fn from_edges(edges: &[(String, String)]) -> Self {
+        let mut graph = Digraph::new();
+        for (u, v) in edges {
+            graph.add_edge(u, v);
+        }
+        graph
+    }
+
+    pub fn node(&mut self, name: &str) -> usize {
+        if let Some(&i) = self.index.get(name) {
+            return i;
+        }
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), self.names.len() - 1);
+        self.out.push(Vec::new());
+        self.inc.push(Vec::new());
+        self.names.len() - 1
+    }
+
+    pub fn add_edge(&mut self, u: &str, v: &str) {
+        let (u, v) = (self.node(u), self.node(v));
+        if !self.out[u].contains(&v) {
+            self.out[u].push(v);
+            self.inc[v].push(u);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    // Tarjan 强连通分量, 迭代实现以避免深递归
+    pub fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (节点, 下一个待访问的出边下标)
+            let mut work = vec![(root, 0)];
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (u, ref mut next)) = work.last_mut() {
+                if let Some(&v) = self.out[u].get(*next) {
+                    *next += 1;
+                    if index[v] == usize::MAX {
+                        index[v] = counter;
+                        low[v] = counter;
+                        counter += 1;
+                        stack.push(v);
+                        on_stack[v] = true;
+                        work.push((v, 0));
+                    } else if on_stack[v] {
+                        low[u] = low[u].min(index[v]);
+                    }
+                    continue;
+                }
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[u]);
+                }
+                if low[u] == index[u] {
+                    let mut component = Vec::new();
+                    while let Some(v) = stack.pop() {
+                        on_stack[v] = false;
+                        component.push(v);
+                        if v == u {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    // 在 allowed 限定的边上是否存在 from 到 to 的路径
+    fn reachable(&self, from: usize, to: usize, allowed: &dyn Fn(usize, usize) -> bool) -> bool {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([from]);
+        seen[from] = true;
+        while let Some(u) = queue.pop_front() {
+            if u == to {
+                return true;
+            }
+            for &v in &self.out[u] {
+                if !seen[v] && allowed(u, v) {
+                    seen[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        false
+    }
+
+    // 分量内从 start 出发回到 start 的一个简单环
+    fn cycle_through(&self, start: usize, member: &[bool]) -> Vec<usize> {
+        let mut parent = vec![usize::MAX; self.len()];
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            for &v in &self.out[u] {
+                if v == start {
+                    let mut cycle = vec![u];
+                    let mut x = u;
+                    while x != start {
+                        x = parent[x];
+                        cycle.push(x);
+                    }
+                    cycle.reverse();
+                    return cycle;
+                }
+                if member[v] && parent[v] == usize::MAX {
+                    parent[v] = u;
+                    queue.push_back(v);
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    // Eades-Lin-Smyth 启发式排序, 逆序边即为反馈边候选
+    fn els_order(&self) -> Vec<usize> {
+        let n = self.len();
+        let mut out_deg: Vec<isize> = self.out.iter().map(|o| o.len() as isize).collect();
+        let mut in_deg: Vec<isize> = self.inc.iter().map(|i| i.len() as isize).collect();
+        let mut removed = vec![false; n];
+        let (mut head, mut tail) = (Vec::new(), Vec::new());
+        let mut remaining = n;
+
+        let remove =
+            |u: usize, removed: &mut [bool], out_deg: &mut [isize], in_deg: &mut [isize]| {
+                removed[u] = true;
+                for &v in &self.out[u] {
+                    in_deg[v] -= 1;
+                }
+                for &w in &self.inc[u] {
+                    out_deg[w] -= 1;
+                }
+            };
+        while remaining > 0 {
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for u in 0..n {
+                    if removed[u] {
+                        continue;
+                    }
+                    // 自环不影响源点与汇点的判定
+                    let self_loop = self.out[u].contains(&u) as isize;
+                    if out_deg[u] - self_loop == 0 {
+                        remove(u, &mut removed, &mut out_deg, &mut in_deg);
+                        tail.push(u);
+                        remaining -= 1;
+                        changed = true;
+                    } else if in_deg[u] - self_loop == 0 {
+                        remove(u, &mut removed, &mut out_deg, &mut in_deg);
+                        head.push(u);
+                        remaining -= 1;
+                        changed = true;
+                    }
+                }
+            }
+            if let Some(u) = (0..n)
+                .filter(|&u| !removed[u])
+                .max_by_key(|&u| (out_deg[u] - in_deg[u], std::cmp::Reverse(u)))
+            {
+                remove(u, &mut removed, &mut out_deg, &mut in_deg);
+                head.push(u);
+                remaining -= 1;
+            }
+        }
+        tail.reverse();
+        head.extend(tail);
+        head
+    }
+}
+
+#[pyfunction]
+pub fn find_cycles(
+    py: Python<'_>,
+    edges: Vec<(String, String)>,
+) -> PyResult<(Vec<Vec<String>>, Vec<(String, String)>)> {
+    py.allow_threads(|| {
+        let graph = Digraph::from_edges(&edges);
+        let n = graph.len();
+
+        let mut cycles = Vec::new();
+        for component in graph.strongly_connected() {
+            let start = component[0];
+            if component.len() == 1 && !graph.out[start].contains(&start) {
+                continue;
+            }
+            let mut member = vec![false; n];
+            for &u in &component {
+                member[u] = true;
+            }
+            let cycle = graph.cycle_through(start, &member);
+            cycles.push(cycle.iter().map(|&u| graph.names[u].clone()).collect());
+        }
+        cycles.sort();
+
+        // 启发式得到反馈边, 再逐条尝试放回, 使结果为极小集合
+        let order = graph.els_order();
+        let mut position = vec![0; n];
+        for (i, &u) in order.iter().enumerate() {
+            position[u] = i;
+        }
+        let mut feedback: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| graph.out[u].iter().map(move |&v| (u, v)))
+            .filter(|&(u, v)| position[u] >= position[v])
+            .collect();
+        feedback.sort_by_key(|&(u, v)| (position[u] - position[v], u, v));
+        let mut removed: Vec<(usize, usize)> = feedback.clone();
+        for edge in feedback {
+            let (u, v) = edge;
+            let rest: Vec<(usize, usize)> =
+                removed.iter().copied().filter(|&e| e != edge).collect();
+            if u != v && !graph.reachable(v, u, &|a, b| !rest.contains(&(a, b))) {
+                removed = rest;
+            }
+        }
+        removed.sort();
+
+        Ok((
+            cycles,
+            removed
+                .into_iter()
+                .map(|(u, v)| (graph.names[u].clone(), graph.names[v].clone()))
+                .collect(),
+        ))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (edges, targets, chapter_order=None))]
+pub fn learning_path(
+    py: Python<'_>,
+    edges: Vec<(String, String)>,
+    targets: Vec<String>,
+    chapter_order: Option<HashMap<String, usize>>,
+) -> PyResult<Vec<String>> {
+    py.allow_threads(|| {
+        let mut graph = Digraph::from_edges(&edges);
+        let targets: Vec<usize> = targets.iter().map(|t| graph.node(t)).collect();
+        let n = graph.len();
+
+        // 目标概念及其全部前驱
+        let mut needed = vec![false; n];
+        let mut queue: VecDeque<usize> = targets.into_iter().collect();
+        for &t in &queue {
+            needed[t] = true;
+        }
+        while let Some(u) = queue.pop_front() {
+            for &p in &graph.inc[u] {
+                if !needed[p] {
+                    needed[p] = true;
+                    queue.push_back(p);
+                }
+            }
+        }
+
+        // 入度为零者中按 (章节序, 首次出现顺序) 优先
+        let chapter_order = chapter_order.unwrap_or_default();
+        let key = |u: usize| {
+            (
+                chapter_order
+                    .get(&graph.names[u])
+                    .copied()
+                    .unwrap_or(usize::MAX),
+                u,
+            )
+        };
+        let mut in_deg: Vec<usize> = (0..n)
+            .map(|u| graph.inc[u].iter().filter(|&&p| needed[p]).count())
+            .collect();
+        let mut ready: BinaryHeap<Reverse<(usize, usize)>> = (0..n)
+            .filter(|&u| needed[u] && in_deg[u] == 0)
+            .map(|u| Reverse(key(u)))
+            .collect();
+        let mut path = Vec::new();
+        while let Some(Reverse((_, u))) = ready.pop() {
+            path.push(graph.names[u].clone());
+            for &v in &graph.out[u] {
+                if needed[v] {
+                    in_deg[v] -= 1;
+                    if in_deg[v] == 0 {
+                        ready.push(Reverse(key(v)));
+                    }
+                }
+            }
+        }
+
+        let total = needed.iter().filter(|&&x| x).count();
+        if path.len() < total {
+            let mut stuck: Vec<&str> = (0..n)
+                .filter(|&u| needed[u] && in_deg[u] > 0)
+                .map(|u| graph.names[u].as_str())
+                .collect();
+            stuck.sort_unstable();
+            return Err(PyValueError::new_err(format!(
+                "先修关系存在环, 以下概念无法排序: {}",
+                stuck.join(", ")
+            )));
+        }
+
+        Ok(path)
+    })
+}
+
+// 从种子节点出发按 direction 做广度优先, 返回各节点的跳数
+pub fn hop_distances(graph: &Digraph, seeds: &[usize], k: usize, direction: &str) -> Vec<usize> {
+    let mut dist = vec![usize::MAX; graph.len()];
+    let mut queue = VecDeque::new();
+    for &s in seeds {
+        dist[s] = 0;
+        queue.push_back(s);
+    }
+    while let Some(u) = queue.pop_front() {
+        if dist[u] == k {
+            continue;
+        }
+        let out = graph.out[u].iter().filter(|_| direction != "in");
+        let inc = graph.inc[u].iter().filter(|_| direction != "out");
+        for &v in out.chain(inc) {
+            if dist[v] == usize::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, seeds, k=1, direction="both"))]
+pub fn subgraph(
+    py: Python<'_>,
+    triples: Vec<(String, String, String)>,
+    seeds: Vec<String>,
+    k: usize,
+    direction: &str,
+) -> PyResult<Vec<(String, String, String)>> {
+    py.allow_threads(|| {
+        check_direction(direction)?;
+        let mut graph = Digraph::new();
+        for (head, _, tail) in &triples {
+            graph.add_edge(head, tail);
+        }
+        let seeds: Vec<usize> = seeds
+            .iter()
+            .filter_map(|s| graph.index.get(s).copied())
+            .collect();
+        let dist = hop_distances(&graph, &seeds, k, direction);
+
+        // 取 k 跳内节点的导出子图
+        let within = |name: &str| dist[graph.index[name]] != usize::MAX;
+        Ok(triples
+            .into_iter()
+            .filter(|(head, _, tail)| within(head) && within(tail))
+            .collect())
+    })
+}
+
+// 弱连通分量, 按规模降序, 同规模按最小节点编号
+pub fn weak_components(graph: &Digraph) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..graph.len()).collect();
+    for u in 0..graph.len() {
+        for &v in &graph.out[u] {
+            let (ru, rv) = (find_root(&mut parent, u), find_root(&mut parent, v));
+            parent[ru.max(rv)] = ru.min(rv);
+        }
+    }
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for u in 0..graph.len() {
+        let root = find_root(&mut parent, u);
+        groups.entry(root).or_default().push(u);
+    }
+    let mut components: Vec<Vec<usize>> = groups.into_values().collect();
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+    components
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, entities=None))]
+pub fn components(
+    py: Python<'_>,
+    triples: Vec<(String, String, String)>,
+    entities: Option<Vec<String>>,
+) -> PyResult<(Vec<Vec<String>>, Vec<String>)> {
+    py.allow_threads(|| {
+        let mut graph = Digraph::new();
+        for (head, _, tail) in &triples {
+            graph.add_edge(head, tail);
+        }
+        for entity in entities.unwrap_or_default() {
+            graph.node(&entity);
+        }
+
+        let mut result = Vec::new();
+        let mut orphans = Vec::new();
+        for component in weak_components(&graph) {
+            let names: Vec<String> = component.iter().map(|&u| graph.names[u].clone()).collect();
+            // 只有自环或没有任何边的实体
+            if names.len() == 1 {
+                orphans.push(names[0].clone());
+            }
+            result.push(names);
+        }
+
+        Ok((result, orphans))
+    })
+}
+
+// 幂迭代 PageRank, 悬挂节点的得分均分给全部节点
+pub fn pagerank_scores(out: &[Vec<usize>], damping: f64, iterations: usize, tol: f64) -> Vec<f64> {
+    let n = out.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let base = 1.0 / n as f64;
+    let mut rank = vec![base; n];
+    for _ in 0..iterations {
+        let dangling: f64 = (0..n).filter(|&u| out[u].is_empty()).map(|u| rank[u]).sum();
+        let mut next = vec![(1.0 - damping) * base + damping * dangling * base; n];
+        for (u, targets) in out.iter().enumerate() {
+            if targets.is_empty() {
+                continue;
+            }
+            let share = damping * rank[u] / targets.len() as f64;
+            for &v in targets {
+                next[v] += share;
+            }
+        }
+        let delta: f64 = rank.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < tol {
+            break;
+        }
+    }
+    rank
+}
+
+// 按得分降序, 同分按节点编号
+pub fn ranked(names: &[String], scores: Vec<f64>) -> Vec<(String, f64)> {
+    let mut result: Vec<(usize, f64)> = scores.into_iter().enumerate().collect();
+    result.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    result
+        .into_iter()
+        .map(|(u, s)| (names[u].clone(), s))
+        .collect()
+}
+
+pub fn check_damping(damping: f64) -> PyResult<()> {
+    if !(0.0..=1.0).contains(&damping) {
+        return Err(PyValueError::new_err(format!(
+            "damping 应在 [0, 1] 内: {}",
+            damping
+        )));
+    }
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (edges, damping=0.85, iterations=100, tol=1e-6))]
+pub fn pagerank(
+    py: Python<'_>,
+    edges: Vec<(String, String)>,
+    damping: f64,
+    iterations: usize,
+    tol: f64,
+) -> PyResult<Vec<(String, f64)>> {
+    py.allow_threads(|| {
+        check_damping(damping)?;
+        let graph = Digraph::from_edges(&edges);
+        let scores = pagerank_scores(&graph.out, damping, iterations, tol);
+        Ok(ranked(&graph.names, scores))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (edges, direction="both"))]
+pub fn degree_centrality(
+    py: Python<'_>,
+    edges: Vec<(String, String)>,
+    direction: &str,
+) -> PyResult<Vec<(String, f64)>> {
+    py.allow_threads(|| {
+        check_direction(direction)?;
+        let graph = Digraph::from_edges(&edges);
+        let scale = if graph.len() > 1 {
+            1.0 / (graph.len() - 1) as f64
+        } else {
+            1.0
+        };
+        let scores = (0..graph.len())
+            .map(|u| {
+                let degree = match direction {
+                    "out" => graph.out[u].len(),
+                    "in" => graph.inc[u].len(),
+                    _ => graph.out[u].len() + graph.inc[u].len(),
+                };
+                degree as f64 * scale
+            })
+            .collect();
+        Ok(ranked(&graph.names, scores))
+    })
+}
+
+// Louvain 的一层局部移动, 返回各节点所属社区 (已重新编号) 与是否发生移动
+fn louvain_move(
+    adj: &[Vec<(usize, f64)>],
+    self_loops: &[f64],
+    resolution: f64,
+) -> (Vec<usize>, bool) {
+    let n = adj.len();
+    let degree: Vec<f64> = (0..n)
+        .map(|u| adj[u].iter().map(|&(_, w)| w).sum::<f64>() + 2.0 * self_loops[u])
+        .collect();
+    let m2: f64 = degree.iter().sum();
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut total = degree.clone();
+    let mut moved_any = false;
+    if m2 == 0.0 {
+        return (community, false);
+    }
+
+    let mut weights: HashMap<usize, f64> = HashMap::new();
+    loop {
+        let mut moved = false;
+        for u in 0..n {
+            let current = community[u];
+            total[current] -= degree[u];
+            weights.clear();
+            for &(v, w) in &adj[u] {
+                *weights.entry(community[v]).or_default() += w;
+            }
+            let gain = |c: usize, w: f64| w - resolution * total[c] * degree[u] / m2;
+            let mut best = (
+                current,
+                gain(current, weights.get(&current).copied().unwrap_or(0.0)),
+            );
+            // 遍历顺序不影响结果: 增益相同时取编号较小的社区
+            for (&c, &w) in &weights {
+                let g = gain(c, w);
+                if g > best.1 + 1e-12 || ((g - best.1).abs() <= 1e-12 && c < best.0) {
+                    best = (c, g);
+                }
+            }
+            total[best.0] += degree[u];
+            if best.0 != current {
+                community[u] = best.0;
+                moved = true;
+                moved_any = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let mut renumber: HashMap<usize, usize> = HashMap::new();
+    for c in community.iter_mut() {
+        let next = renumber.len();
+        *c = *renumber.entry(*c).or_insert(next);
+    }
+    (community, moved_any)
+}
+
+#[pyfunction]
+#[pyo3(signature = (edges, resolution=1.0))]
+pub fn detect_communities(
+    py: Python<'_>,
+    edges: Vec<(String, String)>,
+    resolution: f64,
+) -> PyResult<Vec<Vec<String>>> {
+    py.allow_threads(|| {
+        // 视为无向图, 重复边累加权重
+        let mut graph = Digraph::new();
+        let mut weight: HashMap<(usize, usize), f64> = HashMap::new();
+        for (u, v) in &edges {
+            let (u, v) = (graph.node(u), graph.node(v));
+            *weight.entry((u.min(v), u.max(v))).or_default() += 1.0;
+        }
+        let n = graph.len();
+        let mut adj: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        let mut self_loops = vec![0.0; n];
+        let mut pairs: Vec<((usize, usize), f64)> = weight.into_iter().collect();
+        pairs.sort_by_key(|&(pair, _)| pair);
+        for ((u, v), w) in pairs {
+            if u == v {
+                self_loops[u] += w;
+            } else {
+                adj[u].push((v, w));
+                adj[v].push((u, w));
+            }
+        }
+
+        // 原始节点当前所属的社区
+        let mut membership: Vec<usize> = (0..n).collect();
+        loop {
+            let (community, moved) = louvain_move(&adj, &self_loops, resolution);
+            if !moved {
+                break;
+            }
+            for c in membership.iter_mut() {
+                *c = community[*c];
+            }
+            // 将社区聚合为新图的节点
+            let size = community.iter().max().map_or(0, |&c| c + 1);
+            let mut merged: HashMap<(usize, usize), f64> = HashMap::new();
+            let mut next_loops = vec![0.0; size];
+            for u in 0..adj.len() {
+                next_loops[community[u]] += self_loops[u];
+                for &(v, w) in adj[u].iter().filter(|&&(v, _)| u < v) {
+                    let (cu, cv) = (community[u], community[v]);
+                    if cu == cv {
+                        next_loops[cu] += w;
+                    } else {
+                        *merged.entry((cu.min(cv), cu.max(cv))).or_default() += w;
+                    }
+                }
+            }
+            let mut pairs: Vec<((usize, usize), f64)> = merged.into_iter().collect();
+            pairs.sort_by_key(|&(pair, _)| pair);
+            adj = vec![Vec::new(); size];
+            for ((u, v), w) in pairs {
+                adj[u].push((v, w));
+                adj[v].push((u, w));
+            }
+            self_loops = next_loops;
+        }
+
+        let mut groups: Vec<Vec<String>> =
+            vec![Vec::new(); membership.iter().max().map_or(0, |&c| c + 1)];
+        for (u, &c) in membership.iter().enumerate() {
+            groups[c].push(graph.names[u].clone());
+        }
+        groups.retain(|g| !g.is_empty());
+        groups.sort_by_key(|g| Reverse(g.len()));
+        Ok(groups)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, src, dst, max_depth=None, relations=None, direction="both"))]
+pub fn shortest_path(
+    py: Python<'_>,
+    triples: Vec<(String, String, String)>,
+    src: &str,
+    dst: &str,
+    max_depth: Option<usize>,
+    relations: Option<Vec<String>>,
+    direction: &str,
+) -> PyResult<Option<Vec<(String, String, String)>>> {
+    py.allow_threads(|| {
+        check_direction(direction)?;
+        let allowed: Option<HashSet<String>> = relations.map(|r| r.into_iter().collect());
+
+        // 邻接表中记录三元组下标, 以便按原方向输出路径
+        let mut graph = Digraph::new();
+        let mut adj: Vec<Vec<(usize, usize)>> = Vec::new();
+        for (i, (head, relation, tail)) in triples.iter().enumerate() {
+            if allowed.as_ref().is_some_and(|a| !a.contains(relation)) {
+                continue;
+            }
+            let (h, t) = (graph.node(head), graph.node(tail));
+            adj.resize(graph.len(), Vec::new());
+            if direction != "in" {
+                adj[h].push((t, i));
+            }
+            if direction != "out" {
+                adj[t].push((h, i));
+            }
+        }
+        let (Some(&from), Some(&to)) = (graph.index.get(src), graph.index.get(dst)) else {
+            return Ok(None);
+        };
+
+        let mut prev: Vec<Option<(usize, usize)>> = vec![None; graph.len()];
+        let mut depth = vec![usize::MAX; graph.len()];
+        depth[from] = 0;
+        let mut queue = VecDeque::from([from]);
+        while let Some(u) = queue.pop_front() {
+            if u == to || max_depth.is_some_and(|d| depth[u] >= d) {
+                continue;
+            }
+            for &(v, i) in &adj[u] {
+                if depth[v] == usize::MAX {
+                    depth[v] = depth[u] + 1;
+                    prev[v] = Some((u, i));
+                    queue.push_back(v);
+                }
+            }
+        }
+        if depth[to] == usize::MAX {
+            return Ok(None);
+        }
+
+        let mut path = Vec::new();
+        let mut node = to;
+        while let Some((u, i)) = prev[node] {
+            path.push(triples[i].clone());
+            node = u;
+        }
+        path.reverse();
+        Ok(Some(path))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (chunk_entities, min_count=2, window=1))]
+pub fn cooccurrence_graph(
+    py: Python<'_>,
+    chunk_entities: Vec<Vec<String>>,
+    min_count: usize,
+    window: usize,
+) -> PyResult<Vec<(String, String, usize)>> {
+    py.allow_threads(|| {
+        if window == 0 {
+            return Err(PyValueError::new_err("window 必须大于 0"));
+        }
+        let mut graph = Digraph::new();
+        let chunks: Vec<Vec<usize>> = chunk_entities
+            .iter()
+            .map(|entities| {
+                let mut ids: Vec<usize> = entities
+                    .iter()
+                    .map(|e| e.trim())
+                    .filter(|e| !e.is_empty())
+                    .map(|e| graph.node(e))
+                    .collect();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            })
+            .collect();
+
+        // 以每个文本块为锚点, 与其后 window - 1 个文本块中的实体配对, 同一锚点下每对只计一次
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut pairs: HashSet<(usize, usize)> = HashSet::new();
+        for (i, anchor) in chunks.iter().enumerate() {
+            pairs.clear();
+            for other in &chunks[i..(i + window).min(chunks.len())] {
+                for &a in anchor {
+                    for &b in other.iter().filter(|&&b| b != a) {
+                        pairs.insert((a.min(b), a.max(b)));
+                    }
+                }
+            }
+            for &pair in &pairs {
+                *counts.entry(pair).or_default() += 1;
+            }
+        }
+
+        let mut edges: Vec<((usize, usize), usize)> = counts
+            .into_iter()
+            .filter(|&(_, c)| c >= min_count)
+            .collect();
+        edges.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        Ok(edges
+            .into_iter()
+            .map(|((a, b), c)| (graph.names[a].clone(), graph.names[b].clone(), c))
+            .collect())
+    })
+}
+
+#[pyclass(frozen, get_all)]
+pub struct GraphStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub density: f64,
+    pub degree_buckets: Vec<(String, usize)>,
+    pub relation_counts: Vec<(String, usize)>,
+    pub component_sizes: Vec<usize>,
+}
+
+#[pymethods]
+impl GraphStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "GraphStats(node_count={}, edge_count={}, density={:.4}, relations={}, components={})",
+            self.node_count,
+            self.edge_count,
+            self.density,
+            self.relation_counts.len(),
+            self.component_sizes.len()
+        )
+    }
+}
+
+// 度数按 2 的幂分桶: 1, 2-3, 4-7, ...
+fn degree_bucket(degree: usize) -> usize {
+    (usize::BITS - degree.leading_zeros()) as usize
+}
+
+#[pyfunction]
+pub fn graph_stats(py: Python<'_>, triples: Vec<(String, String, String)>) -> PyResult<GraphStats> {
+    py.allow_threads(|| {
+        // 相同的三元组只计一次
+        let mut seen: HashSet<(&str, &str, &str)> = HashSet::new();
+        // 密度按有序节点对计算, 同一对节点间的多种关系只计一次, 不含自环
+        let mut pairs: HashSet<(&str, &str)> = HashSet::new();
+        let mut graph = Digraph::new();
+        let mut degree: Vec<usize> = Vec::new();
+        let mut relation_index: HashMap<&str, usize> = HashMap::new();
+        let mut relation_counts: Vec<(String, usize)> = Vec::new();
+        for (head, relation, tail) in &triples {
+            if !seen.insert((head, relation, tail)) {
+                continue;
+            }
+            if head != tail {
+                pairs.insert((head, tail));
+            }
+            graph.add_edge(head, tail);
+            let (h, t) = (graph.index[head.as_str()], graph.index[tail.as_str()]);
+            degree.resize(graph.len(), 0);
+            degree[h] += 1;
+            degree[t] += 1;
+            let i = *relation_index.entry(relation).or_insert_with(|| {
+                relation_counts.push((relation.clone(), 0));
+                relation_counts.len() - 1
+            });
+            relation_counts[i].1 += 1;
+        }
+        relation_counts.sort_by_key(|r| Reverse(r.1));
+
+        let mut buckets: Vec<usize> = Vec::new();
+        for &d in &degree {
+            let bucket = degree_bucket(d);
+            if buckets.len() <= bucket {
+                buckets.resize(bucket + 1, 0);
+            }
+            buckets[bucket] += 1;
+        }
+        let degree_buckets = buckets
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .filter(|&(_, count)| count > 0)
+            .map(|(bucket, count)| {
+                let (low, high) = (1usize << (bucket - 1), (1usize << bucket) - 1);
+                let label = if low == high {
+                    low.to_string()
+                } else {
+                    format!("{low}-{high}")
+                };
+                (label, count)
+            })
+            .collect();
+
+        let n = graph.len();
+        let edge_count = seen.len();
+        Ok(GraphStats {
+            node_count: n,
+            edge_count,
+            density: if n < 2 {
+                0.0
+            } else {
+                pairs.len() as f64 / (n * (n - 1)) as f64
+            },
+            degree_buckets,
+            relation_counts,
+            component_sizes: weak_components(&graph).iter().map(Vec::len).collect(),
+        })
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(find_cycles, m)?)?;
+    m.add_function(wrap_pyfunction!(learning_path, m)?)?;
+    m.add_function(wrap_pyfunction!(subgraph, m)?)?;
+    m.add_function(wrap_pyfunction!(components, m)?)?;
+    m.add_function(wrap_pyfunction!(pagerank, m)?)?;
+    m.add_function(wrap_pyfunction!(degree_centrality, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_communities, m)?)?;
+    m.add_function(wrap_pyfunction!(shortest_path, m)?)?;
+    m.add_function(wrap_pyfunction!(cooccurrence_graph, m)?)?;
+    m.add_class::<GraphStats>()?;
+    m.add_function(wrap_pyfunction!(graph_stats, m)?)?;
+    Ok(())
+}
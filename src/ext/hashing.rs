@@ -0,0 +1,53 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use xxhash_rust::{xxh3::xxh3_64, xxh64::xxh64};
+
+use super::threads::install;
+
+#[derive(Clone, Copy)]
+enum Algo {
+    Xxh64,
+    Xxh3,
+    Blake3,
+}
+
+fn parse_algo(algo: &str) -> PyResult<Algo> {
+    match algo {
+        "xxh64" => Ok(Algo::Xxh64),
+        "xxh3" => Ok(Algo::Xxh3),
+        "blake3" => Ok(Algo::Blake3),
+        _ => Err(PyValueError::new_err(format!(
+            "未知的哈希算法: {}, 可选 xxh64 / xxh3 / blake3",
+            algo
+        ))),
+    }
+}
+
+// 统一输出小写十六进制, 便于直接作为缓存键
+fn hash_with(text: &str, algo: Algo) -> String {
+    match algo {
+        Algo::Xxh64 => format!("{:016x}", xxh64(text.as_bytes(), 0)),
+        Algo::Xxh3 => format!("{:016x}", xxh3_64(text.as_bytes())),
+        Algo::Blake3 => blake3::hash(text.as_bytes()).to_hex().to_string(),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, algo="xxh64"))]
+pub fn content_hash(text: &str, algo: &str) -> PyResult<String> {
+    Ok(hash_with(text, parse_algo(algo)?))
+}
+
+#[pyfunction]
+#[pyo3(signature = (texts, algo="xxh64"))]
+pub fn hash_batch(py: Python<'_>, texts: Vec<String>, algo: &str) -> PyResult<Vec<String>> {
+    let algo = parse_algo(algo)?;
+    Ok(py.allow_threads(|| install(|| texts.par_iter().map(|t| hash_with(t, algo)).collect())))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(content_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(hash_batch, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,296 @@
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use regex::Regex;
+
+use super::clean::to_half_width;
+use super::numeral::{is_cn_numeral, is_number_char, parse_cn_num};
+use super::similarity::normalized_similarity;
+use super::threads::install;
+
+// 去掉标题编号后的分隔符
+fn clean_title(s: &str) -> String {
+    s.trim_start_matches([' ', '\t', '\u{3000}', '、', '.', '．', ':', '：'])
+        .trim()
+        .to_string()
+}
+
+// 匹配 "第X章" 一类的前缀
+fn ordinal_heading(line: &str) -> Option<(usize, u64, String)> {
+    let rest = line.strip_prefix('第')?;
+    let num_len: usize = rest
+        .chars()
+        .take_while(|&c| is_number_char(c))
+        .map(char::len_utf8)
+        .sum();
+    let number = parse_cn_num(&rest[..num_len])?;
+    let rest = &rest[num_len..];
+    let units = [
+        ("部分", 0),
+        ("篇", 0),
+        ("编", 0),
+        ("章", 1),
+        ("讲", 1),
+        ("课", 1),
+        ("节", 2),
+    ];
+    for (unit, level) in units {
+        if let Some(title) = rest.strip_prefix(unit) {
+            return Some((level, number, clean_title(title)));
+        }
+    }
+    None
+}
+
+// 匹配 "一、" 与 "（一）"/"（1）" 一类的前缀
+fn enumeration_heading(line: &str) -> Option<(usize, u64, String)> {
+    let (inner, rest, bracketed) = if let Some(rest) = line.strip_prefix(['（', '(']) {
+        let end = rest.find(['）', ')'])?;
+        let close_len = rest[end..].chars().next()?.len_utf8();
+        (&rest[..end], &rest[end + close_len..], true)
+    } else {
+        let end = line.find('、')?;
+        (&line[..end], &line[end + '、'.len_utf8()..], false)
+    };
+    if inner.is_empty() || inner.chars().count() > 4 {
+        return None;
+    }
+    let number = parse_cn_num(inner)?;
+    let level = match (bracketed, inner.chars().all(is_cn_numeral)) {
+        (false, true) => 3,
+        (true, true) => 4,
+        (true, false) => 5,
+        // "1、" 视为与 "一、" 同级
+        (false, false) => 3,
+    };
+    Some((level, number, clean_title(rest)))
+}
+
+#[pyfunction]
+pub fn parse_cn_heading(line: &str) -> PyResult<Option<(usize, u64, String)>> {
+    let line = line.trim();
+    Ok(ordinal_heading(line).or_else(|| enumeration_heading(line)))
+}
+
+// 解析 "3.2.1 标题" 式编号, 返回编号路径与标题
+fn split_numbering(line: &str) -> Option<(Vec<u32>, String)> {
+    let mut path: Vec<u32> = Vec::new();
+    let mut rest = line.trim();
+    let mut dotted = false;
+    loop {
+        let len = rest.chars().take_while(char::is_ascii_digit).count();
+        if len > 4 {
+            return None;
+        }
+        if len == 0 {
+            break;
+        }
+        path.push(rest[..len].parse().ok()?);
+        rest = &rest[len..];
+        match rest.strip_prefix(['.', '．']) {
+            Some(r) => {
+                dotted = true;
+                rest = r;
+                if !r.starts_with(|c: char| c.is_ascii_digit()) {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+    // "3 栈" 这样不带点的编号需要与标题之间有空白, 避免误识别 "2024年"
+    if path.is_empty() || (!dotted && !rest.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    Some((path, rest.trim().to_string()))
+}
+
+// 标题 + 引导符 (点线或空白) + 页码
+static TOC_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(.*?\S)\s*(?:[.·•…．_-]\s*){2,}\s*(\d{1,4})$|^(.*?\S)\s+(\d{1,4})$").unwrap()
+});
+
+#[pyfunction]
+pub fn parse_toc(text_lines: Vec<String>) -> PyResult<Vec<(String, usize, u32)>> {
+    let mut result = Vec::new();
+
+    for line in &text_lines {
+        let line = line.trim();
+        let Some(caps) = TOC_LINE.captures(line) else {
+            continue;
+        };
+        let (title, page) = match (caps.get(1), caps.get(2)) {
+            (Some(t), Some(p)) => (t.as_str(), p.as_str()),
+            _ => (&caps[3], caps.get(4).unwrap().as_str()),
+        };
+        let title = title.trim_end_matches(['.', '·', '•', '…', '．', '_', '-', ' ']);
+        // 纯数字的行 (如页眉页码) 不是目录项
+        if title.is_empty() || title.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            continue;
+        }
+        // 全角页码转为半角, 其他 Unicode 数字无法解析时跳过该行
+        let page: String = page.chars().map(to_half_width).collect();
+        let Ok(page) = page.parse::<u32>() else {
+            continue;
+        };
+        let level = ordinal_heading(title)
+            .or_else(|| enumeration_heading(title))
+            .map(|(level, _, _)| level)
+            .or_else(|| split_numbering(title).map(|(path, _)| path.len()))
+            .unwrap_or(1);
+        result.push((title.to_string(), level, page));
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn parse_numbering(line: &str) -> PyResult<Option<(Vec<u32>, String)>> {
+    Ok(split_numbering(line))
+}
+
+#[pyfunction]
+pub fn build_heading_tree(
+    headings: Vec<String>,
+) -> PyResult<(Vec<(Vec<u32>, String, Option<usize>)>, Vec<(usize, String)>)> {
+    let mut nodes: Vec<(Vec<u32>, String, Option<usize>)> = Vec::new();
+    let mut issues = Vec::new();
+    // 祖先节点下标
+    let mut stack: Vec<usize> = Vec::new();
+    // 各节点最后一个子节点的编号, 末尾一项对应根
+    let mut last_child: Vec<u32> = Vec::new();
+    let mut last_root = 0;
+
+    for (i, heading) in headings.iter().enumerate() {
+        let Some((path, title)) = split_numbering(heading) else {
+            issues.push((i, format!("无法解析编号: {}", heading)));
+            continue;
+        };
+        let index = nodes.len();
+
+        while stack
+            .last()
+            .is_some_and(|&i| nodes[i].0.len() >= path.len())
+        {
+            stack.pop();
+        }
+        let parent = stack.last().copied();
+        let parent_depth = parent.map_or(0, |p| nodes[p].0.len());
+
+        let number = *path.last().unwrap();
+        if path.len() > parent_depth + 1 {
+            issues.push((i, format!("跳级: {} 缺少上级标题", heading)));
+        } else if parent.is_some_and(|p| nodes[p].0[..] != path[..path.len() - 1]) {
+            let p = parent.unwrap();
+            issues.push((
+                i,
+                format!("编号与上级 {:?} 不一致: {}", nodes[p].0, heading),
+            ));
+        } else {
+            let previous = match parent {
+                Some(p) => last_child[p],
+                None => last_root,
+            };
+            if number != previous + 1 {
+                issues.push((i, format!("编号不连续: {} 之后为 {}", previous, number)));
+            }
+        }
+        match parent {
+            Some(p) => last_child[p] = number,
+            None => last_root = number,
+        }
+
+        nodes.push((path, title, parent));
+        last_child.push(0);
+        stack.push(index);
+    }
+
+    Ok((nodes, issues))
+}
+
+pub fn warm_up() {
+    Lazy::force(&TOC_LINE);
+}
+
+// 去掉编号后用于跨版本比较的标题, 忽略空白、全角/半角与大小写差异
+fn comparable_title(line: &str) -> Vec<char> {
+    let line = line.trim();
+    let title = ordinal_heading(line)
+        .or_else(|| enumeration_heading(line))
+        .map(|(_, _, title)| title)
+        .or_else(|| split_numbering(line).map(|(_, title)| title))
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| line.to_string());
+    title
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| to_half_width(c).to_ascii_lowercase())
+        .collect()
+}
+
+#[pyfunction]
+#[pyo3(signature = (outline_a, outline_b, min_similarity=0.6))]
+pub fn match_sections(
+    py: Python<'_>,
+    outline_a: Vec<String>,
+    outline_b: Vec<String>,
+    min_similarity: f64,
+) -> PyResult<Vec<(usize, usize, f64)>> {
+    py.allow_threads(|| {
+        let a: Vec<Vec<char>> = outline_a.iter().map(|t| comparable_title(t)).collect();
+        let b: Vec<Vec<char>> = outline_b.iter().map(|t| comparable_title(t)).collect();
+        // 低于阈值的标题对不允许配对, 记为 0
+        let similarity: Vec<Vec<f64>> = install(|| {
+            a.par_iter()
+                .map(|x| {
+                    b.iter()
+                        .map(|y| match normalized_similarity(x, y, min_similarity) {
+                            s if s >= min_similarity && s > 0.0 => s,
+                            _ => 0.0,
+                        })
+                        .collect()
+                })
+                .collect()
+        });
+
+        // 保持顺序的序列比对, 最大化配对的相似度之和, 空位不扣分
+        let width = b.len() + 1;
+        let mut table = vec![0.0f64; (a.len() + 1) * width];
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let mut best = table[(i - 1) * width + j].max(table[i * width + j - 1]);
+                let s = similarity[i - 1][j - 1];
+                if s > 0.0 {
+                    best = best.max(table[(i - 1) * width + j - 1] + s);
+                }
+                table[i * width + j] = best;
+            }
+        }
+
+        let mut pairs = Vec::new();
+        let (mut i, mut j) = (a.len(), b.len());
+        while i > 0 && j > 0 {
+            let s = similarity[i - 1][j - 1];
+            if s > 0.0 && table[i * width + j] == table[(i - 1) * width + j - 1] + s {
+                pairs.push((i - 1, j - 1, s));
+                i -= 1;
+                j -= 1;
+            } else if table[(i - 1) * width + j] >= table[i * width + j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+        pairs.reverse();
+        Ok(pairs)
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(match_sections, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_cn_heading, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_toc, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_numbering, m)?)?;
+    m.add_function(wrap_pyfunction!(build_heading_tree, m)?)?;
+    Ok(())
+}
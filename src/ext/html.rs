@@ -0,0 +1,322 @@
+use pyo3::prelude::*;
+
+enum Token<'a> {
+    Text(&'a str),
+    // 标签名 (小写), 是否为闭合标签, 是否以 /> 自闭合
+    Tag(String, bool, bool),
+}
+
+const SKIP_TAGS: [&str; 6] = ["script", "style", "noscript", "template", "head", "svg"];
+
+const BLOCK_TAGS: [&str; 32] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+    "caption",
+];
+
+// 内容为原始文本的元素, 其中的 '<' 不开始标签, 直接跳到对应的闭合标签
+const RAW_TEXT_TAGS: [&str; 3] = ["script", "style", "template"];
+
+// 从 from 开始寻找不区分大小写的 </name, 其后须为标签名之外的字符
+fn raw_text_end(lower: &str, from: usize, name: &str) -> Option<usize> {
+    let close = format!("</{}", name);
+    let mut pos = from;
+    while let Some(offset) = lower[pos..].find(&close) {
+        let start = pos + offset;
+        let after = lower[start + close.len()..].chars().next();
+        if after.is_none_or(|c| !c.is_ascii_alphanumeric()) {
+            return Some(start);
+        }
+        pos = start + close.len();
+    }
+    None
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    // ASCII 小写不改变字节偏移
+    let lower = html.to_ascii_lowercase();
+    let bytes = html.as_bytes();
+    let mut i = 0;
+    let mut text_start = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+        let rest = &html[i..];
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|e| e + 3)
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest.find('>').map(|e| e + 1)
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+            tag_end(rest)
+        } else {
+            // 不是标签, 作为普通文本
+            i += 1;
+            continue;
+        };
+        let end = end.unwrap_or(rest.len());
+
+        if text_start < i {
+            tokens.push(Token::Text(&html[text_start..i]));
+        }
+        let raw = &rest[..end];
+        if let Some(inner) = raw.strip_prefix("</") {
+            tokens.push(Token::Tag(tag_name(inner), true, false));
+        } else if !raw.starts_with("<!") && !raw.starts_with("<?") {
+            let name = tag_name(&raw[1..]);
+            let self_closing = raw.ends_with("/>");
+            if !self_closing && RAW_TEXT_TAGS.contains(&name.as_str()) {
+                // 内容整体作为文本, 未闭合时延续到文末
+                let content_start = i + end;
+                let content_end = raw_text_end(&lower, content_start, &name).unwrap_or(html.len());
+                tokens.push(Token::Tag(name, false, false));
+                if content_start < content_end {
+                    tokens.push(Token::Text(&html[content_start..content_end]));
+                }
+                i = content_end;
+                text_start = i;
+                continue;
+            }
+            tokens.push(Token::Tag(name, false, self_closing));
+        }
+        i += end;
+        text_start = i;
+    }
+    if text_start < html.len() {
+        tokens.push(Token::Text(&html[text_start..]));
+    }
+
+    tokens
+}
+
+// 寻找标签结束位置, 跳过引号内的 '>'
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in rest.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(i + 1),
+            None => {}
+        }
+    }
+    None
+}
+
+fn tag_name(inner: &str) -> String {
+    inner
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(pos) = rest.find('&') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = rest[1..].find(';').filter(|&e| e <= 10).and_then(|e| {
+            let name = &rest[1..e + 1];
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "middot" => Some('·'),
+                "hellip" => Some('…'),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "ldquo" => Some('“'),
+                "rdquo" => Some('”'),
+                "lsquo" => Some('‘'),
+                "rsquo" => Some('’'),
+                "times" => Some('×'),
+                "divide" => Some('÷'),
+                "copy" => Some('©'),
+                _ => {
+                    if let Some(hex) = name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                    } else if let Some(dec) = name.strip_prefix('#') {
+                        dec.parse().ok().and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            c.map(|c| (c, e + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[pyfunction]
+pub fn html_to_text(html: &str) -> PyResult<String> {
+    let mut text = String::new();
+    let mut skip_depth = 0usize;
+    let mut pre_depth = 0usize;
+
+    for token in tokenize(html) {
+        match token {
+            Token::Tag(name, closing, self_closing) => {
+                // 自闭合的 <svg/> 没有内容, 也没有对应的闭合标签
+                if SKIP_TAGS.contains(&name.as_str()) {
+                    if closing {
+                        skip_depth = skip_depth.saturating_sub(1);
+                    } else if !self_closing {
+                        skip_depth += 1;
+                    }
+                    continue;
+                }
+                if name == "pre" {
+                    if closing {
+                        pre_depth = pre_depth.saturating_sub(1);
+                    } else if !self_closing {
+                        pre_depth += 1;
+                    }
+                }
+                if BLOCK_TAGS.contains(&name.as_str()) {
+                    text.push('\n');
+                } else if name == "td" || name == "th" {
+                    text.push(' ');
+                }
+            }
+            Token::Text(raw) if skip_depth == 0 => {
+                let decoded = decode_entities(raw);
+                if pre_depth > 0 {
+                    text.push_str(&decoded);
+                } else {
+                    // 保留首尾空白带来的词间隔
+                    if decoded.starts_with(char::is_whitespace) {
+                        text.push(' ');
+                    }
+                    text.push_str(&collapse_whitespace(&decoded));
+                    if decoded.ends_with(char::is_whitespace) {
+                        text.push(' ');
+                    }
+                }
+            }
+            Token::Text(_) => {}
+        }
+    }
+
+    // 去除行首尾空白并压缩空行
+    let mut result = String::new();
+    let mut blank = 0;
+    for line in text.lines().map(|l| l.trim()) {
+        if line.is_empty() {
+            blank += 1;
+            continue;
+        }
+        if !result.is_empty() {
+            result.push_str(if blank > 1 { "\n\n" } else { "\n" });
+        }
+        result.push_str(line);
+        blank = 0;
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn get_html_outline(html: &str) -> PyResult<Vec<(usize, String)>> {
+    let mut result = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut skip_depth = 0usize;
+
+    for token in tokenize(html) {
+        match token {
+            Token::Tag(name, closing, self_closing) => {
+                // 自闭合的 <svg/> 没有内容, 也没有对应的闭合标签
+                if SKIP_TAGS.contains(&name.as_str()) {
+                    if closing {
+                        skip_depth = skip_depth.saturating_sub(1);
+                    } else if !self_closing {
+                        skip_depth += 1;
+                    }
+                    continue;
+                }
+                let level = match name.as_bytes() {
+                    [b'h', d @ b'1'..=b'6'] => (d - b'0') as usize,
+                    _ => continue,
+                };
+                if closing {
+                    if let Some((l, title)) = current.take() {
+                        let title = collapse_whitespace(&title);
+                        if !title.is_empty() {
+                            result.push((l, title));
+                        }
+                    }
+                } else if skip_depth == 0 {
+                    current = Some((level, String::new()));
+                }
+            }
+            Token::Text(raw) => {
+                if let Some((_, title)) = current.as_mut() {
+                    title.push_str(&decode_entities(raw));
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(html_to_text, m)?)?;
+    m.add_function(wrap_pyfunction!(get_html_outline, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use serde::{Deserialize, Serialize};
+
+use super::graph::{check_damping, pagerank_scores, ranked};
+
+// 节点与关系名均驻留为整数编号, 邻接表只存 (关系编号, 节点编号)
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct KnowledgeGraph {
+    pub names: Vec<String>,
+    pub index: HashMap<String, u32>,
+    // 已删除的节点保留编号, 名称从 index 中移除
+    pub alive: Vec<bool>,
+    pub relations: Vec<String>,
+    pub relation_index: HashMap<String, u32>,
+    pub out_edges: Vec<Vec<(u32, u32)>>,
+    pub in_edges: Vec<Vec<(u32, u32)>>,
+    // 全部边 (头, 关系, 尾), 用于插入时判重
+    pub edge_set: HashSet<(u32, u32, u32)>,
+}
+
+impl KnowledgeGraph {
+    pub fn node_id(&self, name: &str) -> Option<usize> {
+        self.index.get(name).map(|&i| i as usize)
+    }
+
+    pub fn require(&self, name: &str) -> PyResult<usize> {
+        self.node_id(name)
+            .ok_or_else(|| PyValueError::new_err(format!("节点不存在: {}", name)))
+    }
+
+    pub fn intern_node(&mut self, name: &str) -> u32 {
+        if let Some(&i) = self.index.get(name) {
+            return i;
+        }
+        let i = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), i);
+        self.alive.push(true);
+        self.out_edges.push(Vec::new());
+        self.in_edges.push(Vec::new());
+        i
+    }
+
+    pub fn intern_relation(&mut self, relation: &str) -> u32 {
+        if let Some(&i) = self.relation_index.get(relation) {
+            return i;
+        }
+        let i = self.relations.len() as u32;
+        self.relations.push(relation.to_string());
+        self.relation_index.insert(relation.to_string(), i);
+        i
+    }
+
+    pub fn insert_edge(&mut self, head: u32, relation: u32, tail: u32) -> bool {
+        if !self.edge_set.insert((head, relation, tail)) {
+            return false;
+        }
+        self.out_edges[head as usize].push((relation, tail));
+        self.in_edges[tail as usize].push((relation, head));
+        true
+    }
+
+    // 存活节点编号
+    pub fn live_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.names.len()).filter(|&i| self.alive[i])
+    }
+
+    // 全部边 (头, 关系, 尾), 按头节点编号与插入顺序
+    pub fn edges(&self) -> impl Iterator<Item = (usize, u32, usize)> + '_ {
+        self.live_nodes().flat_map(move |u| {
+            self.out_edges[u]
+                .iter()
+                .map(move |&(r, v)| (u, r, v as usize))
+        })
+    }
+
+    // 无向邻居 (去重)
+    pub fn undirected_neighbors(&self, node: usize) -> Vec<usize> {
+        let mut result: Vec<usize> = self.out_edges[node]
+            .iter()
+            .chain(&self.in_edges[node])
+            .map(|&(_, v)| v as usize)
+            .filter(|&v| v != node)
+            .collect();
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    fn detach(&mut self, node: usize) {
+        for (r, v) in std::mem::take(&mut self.out_edges[node]) {
+            self.in_edges[v as usize].retain(|&(rr, u)| !(rr == r && u as usize == node));
+            self.edge_set.remove(&(node as u32, r, v));
+        }
+        for (r, u) in std::mem::take(&mut self.in_edges[node]) {
+            self.out_edges[u as usize].retain(|&(rr, v)| !(rr == r && v as usize == node));
+            self.edge_set.remove(&(u, r, node as u32));
+        }
+    }
+}
+
+// 快照文件头: 魔数, 格式版本, 是否 zstd 压缩
+const SNAPSHOT_MAGIC: &[u8; 4] = b"CKG\0";
+const SNAPSHOT_VERSION: u8 = 1;
+
+// 快照只保存存活节点 (重新编号) 与出边, 索引与入边在加载时重建
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    names: Vec<String>,
+    relations: Vec<String>,
+    out_edges: Vec<Vec<(u32, u32)>>,
+}
+
+impl KnowledgeGraph {
+    fn to_snapshot(&self) -> Snapshot {
+        let mut remap = vec![u32::MAX; self.names.len()];
+        for (new, old) in self.live_nodes().enumerate() {
+            remap[old] = new as u32;
+        }
+        Snapshot {
+            names: self.live_nodes().map(|i| self.names[i].clone()).collect(),
+            relations: self.relations.clone(),
+            out_edges: self
+                .live_nodes()
+                .map(|u| {
+                    self.out_edges[u]
+                        .iter()
+                        .map(|&(r, v)| (r, remap[v as usize]))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn from_snapshot(snapshot: Snapshot) -> PyResult<Self> {
+        let n = snapshot.names.len();
+        let invalid = || PyValueError::new_err("快照数据损坏");
+        if snapshot.out_edges.len() != n {
+            return Err(invalid());
+        }
+        let mut in_edges: Vec<Vec<(u32, u32)>> = vec![Vec::new(); n];
+        let mut edge_set = HashSet::new();
+        for (u, edges) in snapshot.out_edges.iter().enumerate() {
+            for &(r, v) in edges {
+                if v as usize >= n
+                    || r as usize >= snapshot.relations.len()
+                    || !edge_set.insert((u as u32, r, v))
+                {
+                    return Err(invalid());
+                }
+                in_edges[v as usize].push((r, u as u32));
+            }
+        }
+        Ok(KnowledgeGraph {
+            index: (0..n as u32)
+                .map(|i| (snapshot.names[i as usize].clone(), i))
+                .collect(),
+            alive: vec![true; n],
+            relation_index: (0..snapshot.relations.len() as u32)
+                .map(|i| (snapshot.relations[i as usize].clone(), i))
+                .collect(),
+            names: snapshot.names,
+            relations: snapshot.relations,
+            out_edges: snapshot.out_edges,
+            in_edges,
+            edge_set,
+        })
+    }
+}
+
+pub fn check_direction(direction: &str) -> PyResult<()> {
+    match direction {
+        "out" | "in" | "both" => Ok(()),
+        _ => Err(PyValueError::new_err(format!(
+            "未知的方向: {}, 应为 'out', 'in' 或 'both'",
+            direction
+        ))),
+    }
+}
+
+#[pymethods]
+impl KnowledgeGraph {
+    #[new]
+    #[pyo3(signature = (triples = None))]
+    fn new(triples: Option<Vec<(String, String, String)>>) -> Self {
+        let mut graph = KnowledgeGraph::default();
+        for (head, relation, tail) in triples.unwrap_or_default() {
+            graph.add_triple(&head, &relation, &tail);
+        }
+        graph
+    }
+
+    fn add_triple(&mut self, head: &str, relation: &str, tail: &str) -> bool {
+        let head = self.intern_node(head);
+        let tail = self.intern_node(tail);
+        let relation = self.intern_relation(relation);
+        self.insert_edge(head, relation, tail)
+    }
+
+    fn add_triples(&mut self, triples: Vec<(String, String, String)>) -> usize {
+        triples
+            .iter()
+            .filter(|(h, r, t)| self.add_triple(h, r, t))
+            .count()
+    }
+
+    fn has_node(&self, node: &str) -> bool {
+        self.index.contains_key(node)
+    }
+
+    #[pyo3(signature = (node, direction = "out"))]
+    fn neighbors(&self, node: &str, direction: &str) -> PyResult<Vec<(String, String)>> {
+        check_direction(direction)?;
+        let i = self.require(node)?;
+        let mut result = Vec::new();
+        if direction != "in" {
+            for &(r, v) in &self.out_edges[i] {
+                result.push((
+                    self.relations[r as usize].clone(),
+                    self.names[v as usize].clone(),
+                ));
+            }
+        }
+        if direction != "out" {
+            for &(r, u) in &self.in_edges[i] {
+                result.push((
+                    self.relations[r as usize].clone(),
+                    self.names[u as usize].clone(),
+                ));
+            }
+        }
+        Ok(result)
+    }
+
+    #[pyo3(signature = (node, direction = "both"))]
+    fn degree(&self, node: &str, direction: &str) -> PyResult<usize> {
+        check_direction(direction)?;
+        let i = self.require(node)?;
+        Ok(match direction {
+            "out" => self.out_edges[i].len(),
+            "in" => self.in_edges[i].len(),
+            _ => self.out_edges[i].len() + self.in_edges[i].len(),
+        })
+    }
+
+    fn remove_node(&mut self, node: &str) -> bool {
+        let Some(i) = self.node_id(node) else {
+            return false;
+        };
+        self.detach(i);
+        self.alive[i] = false;
+        self.index.remove(node);
+        true
+    }
+
+    fn merge_nodes(&mut self, keep: &str, other: &str) -> PyResult<()> {
+        let keep_id = self.require(keep)?;
+        let other_id = self.require(other)?;
+        if keep_id == other_id {
+            return Ok(());
+        }
+        let out_edges = self.out_edges[other_id].clone();
+        let in_edges = self.in_edges[other_id].clone();
+        self.remove_node(other);
+        // 合并后两节点之间的边成为自环, 予以丢弃
+        for (r, v) in out_edges {
+            if v as usize != keep_id && v as usize != other_id {
+                self.insert_edge(keep_id as u32, r, v);
+            }
+        }
+        for (r, u) in in_edges {
+            if u as usize != keep_id && u as usize != other_id {
+                self.insert_edge(u, r, keep_id as u32);
+            }
+        }
+        Ok(())
+    }
+
+    #[pyo3(signature = (damping = 0.85, iterations = 100, tol = 1e-6))]
+    fn pagerank(
+        &self,
+        py: Python<'_>,
+        damping: f64,
+        iterations: usize,
+        tol: f64,
+    ) -> PyResult<Vec<(String, f64)>> {
+        py.allow_threads(|| {
+            check_damping(damping)?;
+            // 压缩掉已删除节点的编号
+            let live: Vec<usize> = self.live_nodes().collect();
+            let mut compact = vec![usize::MAX; self.names.len()];
+            for (i, &u) in live.iter().enumerate() {
+                compact[u] = i;
+            }
+            let out: Vec<Vec<usize>> = live
+                .iter()
+                .map(|&u| {
+                    let mut targets: Vec<usize> = self.out_edges[u]
+                        .iter()
+                        .map(|&(_, v)| compact[v as usize])
+                        .collect();
+                    targets.sort_unstable();
+                    targets.dedup();
+                    targets
+                })
+                .collect();
+            let names: Vec<String> = live.iter().map(|&u| self.names[u].clone()).collect();
+            Ok(ranked(
+                &names,
+                pagerank_scores(&out, damping, iterations, tol),
+            ))
+        })
+    }
+
+    #[pyo3(signature = (path, compress = true))]
+    fn save_graph(&self, py: Python<'_>, path: &str, compress: bool) -> PyResult<()> {
+        let snapshot = self.to_snapshot();
+        py.allow_threads(|| {
+            let payload = bincode::serialize(&snapshot)
+                .map_err(|e| PyValueError::new_err(format!("序列化失败: {}", e)))?;
+            let payload = if compress {
+                zstd::encode_all(payload.as_slice(), 3)
+                    .map_err(|e| PyValueError::new_err(format!("压缩失败: {}", e)))?
+            } else {
+                payload
+            };
+            let mut content = Vec::with_capacity(payload.len() + 6);
+            content.extend_from_slice(SNAPSHOT_MAGIC);
+            content.push(SNAPSHOT_VERSION);
+            content.push(compress as u8);
+            content.extend_from_slice(&payload);
+            fs::write(path, content)
+                .map_err(|e| PyValueError::new_err(format!("无法写入 {}: {}", path, e)))
+        })
+    }
+
+    #[staticmethod]
+    fn load_graph(py: Python<'_>, path: &str) -> PyResult<Self> {
+        py.allow_threads(|| {
+            let content = fs::read(path)
+                .map_err(|e| PyValueError::new_err(format!("无法读取 {}: {}", path, e)))?;
+            let Some((header, payload)) = content.split_at_checked(6) else {
+                return Err(PyValueError::new_err(format!("不是图谱快照文件: {}", path)));
+            };
+            if &header[..4] != SNAPSHOT_MAGIC {
+                return Err(PyValueError::new_err(format!("不是图谱快照文件: {}", path)));
+            }
+            if header[4] != SNAPSHOT_VERSION {
+                return Err(PyValueError::new_err(format!(
+                    "不支持的快照版本: {}",
+                    header[4]
+                )));
+            }
+            let decompressed;
+            let payload = if header[5] == 1 {
+                decompressed = zstd::decode_all(payload)
+                    .map_err(|e| PyValueError::new_err(format!("解压失败: {}", e)))?;
+                decompressed.as_slice()
+            } else {
+                payload
+            };
+            let snapshot: Snapshot = bincode::deserialize(payload)
+                .map_err(|e| PyValueError::new_err(format!("快照数据损坏: {}", e)))?;
+            KnowledgeGraph::from_snapshot(snapshot)
+        })
+    }
+
+    fn nodes(&self) -> Vec<String> {
+        self.live_nodes().map(|i| self.names[i].clone()).collect()
+    }
+
+    fn triples(&self) -> Vec<(String, String, String)> {
+        self.edges()
+            .map(|(u, r, v)| {
+                (
+                    self.names[u].clone(),
+                    self.relations[r as usize].clone(),
+                    self.names[v].clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn to_edge_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        // 每个节点名与关系名只创建一次 Python 字符串, 各边共享引用
+        let mut names: Vec<Option<Bound<'py, PyString>>> = vec![None; self.names.len()];
+        let relations: Vec<Bound<'py, PyString>> = self
+            .relations
+            .iter()
+            .map(|r| PyString::new_bound(py, r))
+            .collect();
+        let mut name = |i: usize| {
+            names[i]
+                .get_or_insert_with(|| PyString::new_bound(py, &self.names[i]))
+                .clone()
+        };
+        let key = PyString::new_bound(py, "relation");
+        let list = PyList::empty_bound(py);
+        for (u, r, v) in self.edges() {
+            let relation = &relations[r as usize];
+            let attrs = PyDict::new_bound(py);
+            attrs.set_item(&key, relation)?;
+            let edge = PyTuple::new_bound(
+                py,
+                [
+                    name(u).into_any(),
+                    relation.clone().into_any(),
+                    name(v).into_any(),
+                    attrs.into_any(),
+                ],
+            );
+            list.append(edge)?;
+        }
+        Ok(list)
+    }
+
+    fn node_count(&self) -> usize {
+        self.index.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_set.len()
+    }
+
+    fn __len__(&self) -> usize {
+        self.index.len()
+    }
+
+    fn __contains__(&self, node: &str) -> bool {
+        self.index.contains_key(node)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "KnowledgeGraph(nodes={}, edges={})",
+            self.index.len(),
+            self.edge_set.len()
+        )
+    }
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<KnowledgeGraph>()?;
+    Ok(())
+}
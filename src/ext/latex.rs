@@ -0,0 +1,175 @@
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use regex::Regex;
+
+// 级别与 parse_cn_heading 对应: part 0, chapter 1, section 2, 依次递增
+const SECTIONING: [&str; 7] = [
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+];
+
+// 原样输出的环境, 其中的内容不是 LaTeX 命令
+const VERBATIM_ENVS: [&str; 5] = ["verbatim", "lstlisting", "minted", "comment", "Verbatim"];
+
+// 标题中连同参数一起去除的命令
+const DROP_COMMANDS: [&str; 5] = ["label", "footnote", "index", "thanks", "protect"];
+
+static SECTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\\(part|chapter|section|subsection|subsubsection|paragraph|subparagraph)\b\*?")
+        .unwrap()
+});
+
+static ENV: Lazy<Regex> = Lazy::new(|| Regex::new(r"\\(begin|end)\s*\{([A-Za-z]+)\*?\}").unwrap());
+
+// 去除注释与原样输出环境, '%' 前为奇数个反斜杠时是转义字符
+fn strip_comments(tex: &str) -> String {
+    let mut result = String::with_capacity(tex.len());
+    let mut verbatim: Option<String> = None;
+    for line in tex.lines() {
+        if let Some(env) = &verbatim {
+            let ended = ENV
+                .captures_iter(line)
+                .any(|cap| &cap[1] == "end" && &cap[2] == env.as_str());
+            if ended {
+                verbatim = None;
+            }
+            result.push('\n');
+            continue;
+        }
+        let mut end = line.len();
+        let mut backslashes = 0;
+        for (i, c) in line.char_indices() {
+            match c {
+                '\\' => backslashes += 1,
+                '%' if backslashes % 2 == 0 => {
+                    end = i;
+                    break;
+                }
+                _ => backslashes = 0,
+            }
+        }
+        let line = &line[..end];
+        verbatim = ENV
+            .captures_iter(line)
+            .filter(|cap| &cap[1] == "begin" && VERBATIM_ENVS.contains(&&cap[2]))
+            .last()
+            .map(|cap| cap[2].to_string());
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+// 从 start 处的 open 开始, 返回配对的内容与其后的字节位置
+fn balanced(text: &str, start: usize, open: char, close: char) -> Option<(&str, usize)> {
+    if !text[start..].starts_with(open) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&text[start + 1..start + i], start + i + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn skip_whitespace(text: &str, pos: usize) -> usize {
+    pos + text[pos..].len() - text[pos..].trim_start().len()
+}
+
+// 标题转为纯文本: 去除格式命令保留其参数, 去除 label 等命令及其参数
+fn title_text(title: &str) -> String {
+    let mut text = String::new();
+    let mut pos = 0;
+    while let Some(c) = title[pos..].chars().next() {
+        match c {
+            '\\' => {
+                let rest = &title[pos + 1..];
+                let name_len = rest
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(rest.len());
+                if name_len == 0 {
+                    // \\ 换行, \% \& \_ 等转义字符
+                    match rest.chars().next() {
+                        Some('\\') | None => text.push(' '),
+                        Some(c) => text.push(c),
+                    }
+                    pos += 1 + rest.chars().next().map_or(0, char::len_utf8);
+                    continue;
+                }
+                let name = &rest[..name_len];
+                pos += 1 + name_len;
+                if title[pos..].starts_with('*') {
+                    pos += 1;
+                }
+                if DROP_COMMANDS.contains(&name) {
+                    let start = skip_whitespace(title, pos);
+                    if let Some((_, end)) = balanced(title, start, '[', ']') {
+                        pos = end;
+                    }
+                    let start = skip_whitespace(title, pos);
+                    if let Some((_, end)) = balanced(title, start, '{', '}') {
+                        pos = end;
+                    }
+                }
+            }
+            '~' => {
+                text.push(' ');
+                pos += 1;
+            }
+            '{' | '}' | '$' => pos += 1,
+            c => {
+                text.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[pyfunction]
+pub fn get_latex_outline(tex: &str) -> PyResult<Vec<(usize, String)>> {
+    let tex = strip_comments(tex);
+    let mut result = Vec::new();
+    for m in SECTION.find_iter(&tex) {
+        let name = m.as_str()[1..].trim_end_matches('*');
+        let level = SECTIONING.iter().position(|s| *s == name).unwrap();
+        // 跳过可选的短标题 [...]
+        let mut pos = skip_whitespace(&tex, m.end());
+        if let Some((_, end)) = balanced(&tex, pos, '[', ']') {
+            pos = skip_whitespace(&tex, end);
+        }
+        let Some((title, _)) = balanced(&tex, pos, '{', '}') else {
+            continue;
+        };
+        let title = title_text(title);
+        if !title.is_empty() {
+            result.push((level, title));
+        }
+    }
+    Ok(result)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(get_latex_outline, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use unicode_segmentation::GraphemeCursor;
+
+use super::threads::install;
+
+// 按字符 (Unicode 标量值) 计数, 与 Python 的 len(str) 一致;
+// 统计非 UTF-8 续字节的数量, 运行时选择 SIMD 实现, 长文本上明显快于 chars().count()
+pub fn char_len(s: &str) -> usize {
+    bytecount::num_chars(s.as_bytes())
+}
+
+// 末尾 n 个字符的切片, 从尾部回溯而不必统计整串长度
+pub fn char_tail(s: &str, n: usize) -> &str {
+    if n == 0 {
+        return "";
+    }
+    match s.char_indices().rev().nth(n - 1) {
+        Some((i, _)) => &s[i..],
+        None => s,
+    }
+}
+
+// 不晚于字节偏移 i 的最近字素簇边界, 用于避免拆开 emoji 序列与组合字符
+pub fn grapheme_floor(s: &str, i: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(i, s.len(), true);
+    match cursor.is_boundary(s, 0) {
+        Ok(true) => i,
+        _ => cursor.prev_boundary(s, 0).ok().flatten().unwrap_or(0),
+    }
+}
+
+// 不早于字节偏移 i 的最近字素簇边界
+pub fn grapheme_ceil(s: &str, i: usize) -> usize {
+    let mut cursor = GraphemeCursor::new(i, s.len(), true);
+    match cursor.is_boundary(s, 0) {
+        Ok(true) => i,
+        _ => cursor.next_boundary(s, 0).ok().flatten().unwrap_or(s.len()),
+    }
+}
+
+#[pyfunction]
+pub fn char_len_batch(py: Python<'_>, texts: Vec<String>) -> PyResult<Vec<usize>> {
+    Ok(py.allow_threads(|| install(|| texts.par_iter().map(|t| char_len(t)).collect())))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(char_len_batch, m)?)?;
+    Ok(())
+}
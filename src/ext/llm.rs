@@ -0,0 +1,815 @@
+use std::collections::{BTreeMap, HashMap};
+
+use log::debug;
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use regex::Regex;
+use serde_json::Value;
+
+use super::clean::is_cjk;
+use super::config::CoreConfig;
+use super::errors::parse_error;
+use super::json::json_to_py;
+use super::similarity::normalized_similarity;
+use super::stats::is_punct;
+use super::threads::install;
+use super::triples::normalize_key;
+
+// 行内的 (头实体, 关系, 尾实体), 兼容全角括号
+static TUPLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[(（]([^()（）]*)[)）]").unwrap());
+static TABLE_SEPARATOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\|?\s*:?-+:?\s*(\|\s*:?-+:?\s*)*\|?$").unwrap());
+
+const HEAD_KEYS: [&str; 5] = ["head", "subject", "头实体", "主体", "h"];
+const RELATION_KEYS: [&str; 4] = ["relation", "predicate", "关系", "r"];
+const TAIL_KEYS: [&str; 5] = ["tail", "object", "尾实体", "客体", "t"];
+
+// 去除首尾空白与引号, 压缩内部空白
+fn clean_field(field: &str) -> String {
+    field
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\'' | '“' | '”' | '‘' | '’' | '`' | '「' | '」'))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn make_triple(fields: &[&str]) -> Result<(String, String, String), String> {
+    if fields.len() != 3 {
+        return Err(format!("应有 3 个字段, 实际为 {} 个", fields.len()));
+    }
+    let (h, r, t) = (
+        clean_field(fields[0]),
+        clean_field(fields[1]),
+        clean_field(fields[2]),
+    );
+    if h.is_empty() || r.is_empty() || t.is_empty() {
+        return Err("存在空字段".to_string());
+    }
+    Ok((h, r, t))
+}
+
+fn parse_tuples(text: &str) -> (Vec<(String, String, String)>, Vec<String>) {
+    let mut triples = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for cap in TUPLE.captures_iter(line) {
+            let fields: Vec<&str> = cap[1].split([',', '，']).collect();
+            match make_triple(&fields) {
+                Ok(triple) => triples.push(triple),
+                // 不含逗号的括号多为正文中的注释, 除非整行只有这一个元组
+                Err(_) if fields.len() < 2 && line.trim() != cap[0].trim() => {}
+                Err(e) => errors.push(format!("第 {} 行: {}: {}", i + 1, e, cap[0].trim())),
+            }
+        }
+    }
+    (triples, errors)
+}
+
+fn json_field<'a>(item: &'a serde_json::Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+    item.iter()
+        .find(|(k, _)| keys.iter().any(|key| k.trim().eq_ignore_ascii_case(key)))
+        .map(|(_, v)| v)
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_json(text: &str) -> (Vec<(String, String, String)>, Vec<String>) {
+    let (Some(start), Some(end)) = (text.find('['), text.rfind(']')) else {
+        return (Vec::new(), vec!["未找到 JSON 数组".to_string()]);
+    };
+    let items = match serde_json::from_str::<Value>(&text[start..=end.max(start)]) {
+        Ok(Value::Array(items)) => items,
+        Ok(_) => return (Vec::new(), vec!["JSON 不是数组".to_string()]),
+        Err(e) => {
+            // serde_json 的行号从数组起始处计
+            let line = text[..start].matches('\n').count() + e.line();
+            return (
+                Vec::new(),
+                vec![format!("第 {} 行: JSON 解析失败: {}", line, e)],
+            );
+        }
+    };
+
+    let mut triples = Vec::new();
+    let mut errors = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let fields: Result<Vec<String>, String> = match item {
+            Value::Object(map) => [&HEAD_KEYS[..], &RELATION_KEYS, &TAIL_KEYS]
+                .iter()
+                .map(|keys| {
+                    json_field(map, keys)
+                        .map(json_text)
+                        .ok_or_else(|| format!("缺少字段 {}", keys[0]))
+                })
+                .collect(),
+            Value::Array(values) => Ok(values.iter().map(json_text).collect()),
+            _ => Err("应为对象或数组".to_string()),
+        };
+        let result =
+            fields.and_then(|f| make_triple(&f.iter().map(String::as_str).collect::<Vec<_>>()));
+        match result {
+            Ok(triple) => triples.push(triple),
+            Err(e) => errors.push(format!("第 {} 项: {}", i + 1, e)),
+        }
+    }
+    (triples, errors)
+}
+
+fn table_cells(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(str::trim).collect()
+}
+
+fn parse_table(text: &str) -> (Vec<(String, String, String)>, Vec<String>) {
+    let mut triples = Vec::new();
+    let mut errors = Vec::new();
+    // 表头中可识别的列位置, 未识别时按前三列
+    let mut columns: Option<[usize; 3]> = None;
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('|') || TABLE_SEPARATOR.is_match(trimmed) {
+            continue;
+        }
+        let cells = table_cells(trimmed);
+        let position = |keys: &[&str]| {
+            cells
+                .iter()
+                .position(|c| keys.iter().any(|k| clean_field(c).eq_ignore_ascii_case(k)))
+        };
+        if let (Some(h), Some(r), Some(t)) = (
+            position(&HEAD_KEYS),
+            position(&RELATION_KEYS),
+            position(&TAIL_KEYS),
+        ) {
+            columns = Some([h, r, t]);
+            continue;
+        }
+        let fields: Vec<&str> = match columns {
+            Some(cols) if cols.iter().all(|&c| c < cells.len()) => {
+                cols.iter().map(|&c| cells[c]).collect()
+            }
+            Some(_) => {
+                errors.push(format!("第 {} 行: 列数不足: {}", i + 1, trimmed));
+                continue;
+            }
+            None => cells,
+        };
+        match make_triple(&fields) {
+            Ok(triple) => triples.push(triple),
+            Err(e) => errors.push(format!("第 {} 行: {}: {}", i + 1, e, trimmed)),
+        }
+    }
+    (triples, errors)
+}
+
+// 按格式解析三元组, 同时返回实际采用的格式, 格式不合法时返回 None
+fn triples_by_format<'a>(
+    text: &str,
+    format: &'a str,
+) -> Option<(&'a str, (Vec<(String, String, String)>, Vec<String>))> {
+    let format = match format {
+        "auto" => {
+            if text
+                .lines()
+                .any(|l| l.contains('|') && TABLE_SEPARATOR.is_match(l.trim()))
+            {
+                "table"
+            } else if text.trim_start().starts_with('[')
+                || text.contains("```json")
+                || text
+                    .find('[')
+                    .is_some_and(|i| text[i + 1..].trim_start().starts_with(['{', '[']))
+            {
+                "json"
+            } else {
+                "tuple"
+            }
+        }
+        other => other,
+    };
+    let parsed = match format {
+        "tuple" => parse_tuples(text),
+        "json" => parse_json(text),
+        "table" => parse_table(text),
+        _ => return None,
+    };
+    Some((format, parsed))
+}
+
+fn unknown_format(format: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "未知的格式: {format}, 可选 auto / tuple / json / table"
+    ))
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, format="auto"))]
+pub fn parse_triples(
+    text: &str,
+    format: &str,
+) -> PyResult<(Vec<(String, String, String)>, Vec<String>)> {
+    let (format, parsed) = triples_by_format(text, format).ok_or_else(|| unknown_format(format))?;
+    debug!("按 {} 格式解析三元组", format);
+    Ok(parsed)
+}
+
+// 列表项标记: - / * / • / 1. / 1) / 1、 / (1) / （一） / 一、 / ①
+static LIST_ITEM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:[-*•·+]\s+|\d{1,3}\s*[.．、)）]\s*|[(（]\s*(?:\d{1,3}|[一二三四五六七八九十]{1,3})\s*[)）]\s*|[一二三四五六七八九十]{1,3}\s*[、.．]\s*|[①-⑳]\s*)(.*)$",
+    )
+    .unwrap()
+});
+
+#[pyfunction]
+pub fn parse_enumerated(text: &str) -> PyResult<Vec<String>> {
+    let mut items: Vec<String> = Vec::new();
+    // 当前项之后是否可以续接下一行 (遇到空行后不再续接)
+    let mut open = false;
+    for line in text.lines() {
+        let item = LIST_ITEM.captures(line).and_then(|cap| {
+            let content = cap.get(1).unwrap().as_str();
+            let marker = cap[0][..cap[0].len() - content.len()].trim_end();
+            // "1.5 秒" 之类的小数不是列表项
+            let decimal =
+                marker.ends_with(['.', '．']) && content.starts_with(|c: char| c.is_ascii_digit());
+            (!decimal).then_some(content)
+        });
+        match item {
+            Some(content) => {
+                items.push(content.trim().trim_matches('*').trim().to_string());
+                open = true;
+            }
+            // 只有缩进的行才是续行, 顶格的说明文字与结束语结束列表
+            None if line.trim().is_empty()
+                || !line.starts_with(char::is_whitespace)
+                || EPILOGUE.is_match(line) =>
+            {
+                open = false
+            }
+            None if open => {
+                let last = items.last_mut().unwrap();
+                if !(last.is_empty() || last.ends_with(is_cjk)) {
+                    last.push(' ');
+                }
+                last.push_str(line.trim());
+            }
+            None => {}
+        }
+    }
+    items.retain(|item| !item.is_empty());
+    Ok(items)
+}
+
+// 字段名后跟中英文冒号, 允许前置的列表标记与加粗
+fn key_value_pattern(keys: &[String]) -> PyResult<Regex> {
+    let names: Vec<String> = keys.iter().map(|k| regex::escape(k.trim())).collect();
+    Regex::new(&format!(
+        r"^\s*(?:[-*•]\s*)?(?:\*\*)?({})(?:\*\*)?\s*[:：]\s*(?:\*\*)?\s*(.*)$",
+        names.join("|")
+    ))
+    .map_err(|e| PyValueError::new_err(format!("字段名不合法: {}", e)))
+}
+
+fn scan_key_values(text: &str, field: &Regex) -> BTreeMap<String, String> {
+    let mut result: BTreeMap<String, String> = BTreeMap::new();
+    // 当前字段名, 其值可跨行延续到下一个字段或空行
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        if let Some(cap) = field.captures(line) {
+            let key = cap[1].to_string();
+            let value = cap[2].trim().to_string();
+            // 同一字段出现多次时保留第一次
+            if result.contains_key(&key) {
+                current = None;
+                continue;
+            }
+            result.insert(key.clone(), value);
+            current = Some(key);
+        } else if line.trim().is_empty() {
+            current = None;
+        } else if let Some(key) = &current {
+            let value = result.get_mut(key).unwrap();
+            if !(value.is_empty() || value.ends_with(is_cjk)) {
+                value.push(' ');
+            }
+            value.push_str(line.trim());
+        }
+    }
+    for value in result.values_mut() {
+        *value = value.trim_end_matches("**").trim().to_string();
+    }
+    result
+}
+
+#[pyfunction]
+pub fn parse_key_values(text: &str, keys: Vec<String>) -> PyResult<BTreeMap<String, String>> {
+    if keys.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(scan_key_values(text, &key_value_pattern(&keys)?))
+}
+
+// 类型名: str / int / float / bool / list / dict / null, 以及 list[T]
+fn check_type(value: &Value, ty: &str, path: &str, errors: &mut Vec<String>) -> PyResult<()> {
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("list[").and_then(|t| t.strip_suffix(']')) {
+        let Value::Array(items) = value else {
+            errors.push(format!("{path}: 应为 list, 实际为 {}", type_name(value)));
+            return Ok(());
+        };
+        for (i, item) in items.iter().enumerate() {
+            check_type(item, inner, &format!("{path}[{i}]"), errors)?;
+        }
+        return Ok(());
+    }
+    let ok = match ty {
+        "str" | "string" => value.is_string(),
+        "int" | "integer" => value.is_i64() || value.is_u64(),
+        "float" | "number" => value.is_number(),
+        "bool" | "boolean" => value.is_boolean(),
+        "list" | "array" => value.is_array(),
+        "dict" | "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => return Err(PyValueError::new_err(format!("未知的类型: {ty}"))),
+    };
+    if !ok {
+        errors.push(format!("{path}: 应为 {ty}, 实际为 {}", type_name(value)));
+    }
+    Ok(())
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "float",
+        Value::Number(_) => "int",
+        Value::String(_) => "str",
+        Value::Array(_) => "list",
+        Value::Object(_) => "dict",
+    }
+}
+
+// 按 "a.b" 形式的字段路径取值
+fn lookup<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(value, |v, key| v.as_object()?.get(key))
+}
+
+fn validate_object(
+    value: &Value,
+    path: &str,
+    required_fields: &[String],
+    field_types: &HashMap<String, String>,
+    allow_extra: bool,
+    errors: &mut Vec<String>,
+) -> PyResult<()> {
+    let Value::Object(map) = value else {
+        errors.push(format!("{path}: 应为 dict, 实际为 {}", type_name(value)));
+        return Ok(());
+    };
+    for field in required_fields {
+        if lookup(value, field).is_none() {
+            errors.push(format!("{path}.{field}: 缺少字段"));
+        }
+    }
+    let mut typed: Vec<(&String, &String)> = field_types.iter().collect();
+    typed.sort();
+    for (field, ty) in typed {
+        if let Some(v) = lookup(value, field) {
+            check_type(v, ty, &format!("{path}.{field}"), errors)?;
+        }
+    }
+    if !allow_extra {
+        let known = |key: &str| {
+            required_fields
+                .iter()
+                .chain(field_types.keys())
+                .any(|f| f.split('.').next() == Some(key))
+        };
+        for key in map.keys().filter(|k| !known(k)) {
+            errors.push(format!("{path}.{key}: 多余的字段"));
+        }
+    }
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (json_str, required_fields, field_types=None, allow_extra=false))]
+pub fn validate_extraction(
+    json_str: &str,
+    required_fields: Vec<String>,
+    field_types: Option<HashMap<String, String>>,
+    allow_extra: bool,
+) -> PyResult<Vec<String>> {
+    // 整体解析失败时退回到首个括号与末个括号之间的部分
+    let trimmed = json_str.trim();
+    let parsed = serde_json::from_str::<Value>(trimmed).or_else(|e| {
+        let start = trimmed.find(['{', '[']);
+        let end = trimmed.rfind(['}', ']']);
+        match (start, end) {
+            (Some(s), Some(t)) if s < t => {
+                debug!("整体 JSON 解析失败 ({}), 改为解析第 {} 至 {} 字节", e, s, t);
+                serde_json::from_str::<Value>(&trimmed[s..=t])
+            }
+            _ => Err(e),
+        }
+    });
+    let value = match parsed {
+        Ok(value) => value,
+        Err(e) => return Ok(vec![format!("$: JSON 解析失败: {}", e)]),
+    };
+
+    let field_types = field_types.unwrap_or_default();
+    let mut errors = Vec::new();
+    // 顶层为数组时逐项校验
+    match &value {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let path = format!("$[{i}]");
+                validate_object(
+                    item,
+                    &path,
+                    &required_fields,
+                    &field_types,
+                    allow_extra,
+                    &mut errors,
+                )?;
+            }
+        }
+        _ => validate_object(
+            &value,
+            "$",
+            &required_fields,
+            &field_types,
+            allow_extra,
+            &mut errors,
+        )?,
+    }
+    Ok(errors)
+}
+
+static FENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)```[ \t]*([\w+-]*)[^\n]*\n(.*?)(?:\n[ \t]*```|$)").unwrap());
+// 开场白: "好的，以下是提取结果：" / "Sure, here is ..."
+static PREAMBLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*(?:好的|好|当然|没问题|以下|下面|根据|sure|ok(?:ay)?|here|certainly|below)|[:：]\s*$").unwrap()
+});
+// 结束语: "以上是…" / "希望对你有帮助" / "注: …"
+static EPILOGUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*(?:以上|上述|希望|如有|如果|注[:：意]|说明[:：]|备注|总结|note|hope|let me know|if you)").unwrap()
+});
+
+// 从 start 处的括号开始找到与之配对的括号, 忽略字符串内的括号
+fn balanced_end(text: &str, start: usize) -> Option<usize> {
+    let mut stack: Vec<char> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => stack.push('}'),
+            '[' => stack.push(']'),
+            '}' | ']' => {
+                if stack.pop() != Some(c) {
+                    return None;
+                }
+                if stack.is_empty() {
+                    return Some(start + i + c.len_utf8());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[pyfunction]
+pub fn isolate_structured(text: &str) -> PyResult<String> {
+    // 代码块优先, 有多个时取首个标注了语言的
+    let fences: Vec<_> = FENCE.captures_iter(text).collect();
+    if let Some(cap) = fences
+        .iter()
+        .find(|cap| !cap[1].is_empty())
+        .or(fences.first())
+    {
+        debug!(
+            "共 {} 个代码块, 取语言标注为 {:?} 的代码块",
+            fences.len(),
+            &cap[1]
+        );
+        return Ok(cap[2].trim().to_string());
+    }
+
+    // 其次取首个配对完整且能解析的 JSON 对象或数组
+    for (start, _) in text.match_indices(['{', '[']) {
+        if let Some(end) = balanced_end(text, start) {
+            let candidate = &text[start..end];
+            if serde_json::from_str::<Value>(candidate).is_ok() {
+                debug!("未找到代码块, 取第 {} 字节处的 JSON", start);
+                return Ok(candidate.to_string());
+            }
+        }
+    }
+
+    // 最后按空行分段, 去掉开头的开场白与末尾的结束语
+    let mut paragraphs: Vec<&str> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    while paragraphs.len() > 1 && EPILOGUE.is_match(paragraphs[paragraphs.len() - 1]) {
+        debug!("去除结束语: {}", paragraphs[paragraphs.len() - 1]);
+        paragraphs.pop();
+    }
+    if paragraphs.len() > 1 && PREAMBLE.is_match(paragraphs[0].lines().next().unwrap_or("")) {
+        debug!("去除开场白: {}", paragraphs[0]);
+        paragraphs.remove(0);
+    }
+    // 只有一段时逐行去除首尾的开场白与结束语
+    if let [paragraph] = paragraphs.as_slice() {
+        let mut lines: Vec<&str> = paragraph.lines().collect();
+        while lines.len() > 1 && EPILOGUE.is_match(lines[lines.len() - 1]) {
+            debug!("去除结束语: {}", lines[lines.len() - 1]);
+            lines.pop();
+        }
+        if lines.len() > 1 && PREAMBLE.is_match(lines[0]) {
+            debug!("去除开场白: {}", lines[0]);
+            lines.remove(0);
+        }
+        return Ok(lines.join("\n").trim().to_string());
+    }
+    Ok(paragraphs.join("\n\n"))
+}
+
+// 依次取出配对完整且能解析的顶层 JSON 对象或数组, 解析成功后从其末尾继续
+fn all_json(text: &str) -> Vec<Value> {
+    let mut values = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = text[pos..].find(['{', '[']) {
+        let start = pos + offset;
+        let parsed = balanced_end(text, start).and_then(|end| {
+            serde_json::from_str::<Value>(&text[start..end])
+                .ok()
+                .map(|v| (v, end))
+        });
+        match parsed {
+            Some((value, end)) => {
+                values.push(value);
+                pos = end;
+            }
+            None => pos = start + 1,
+        }
+    }
+    values
+}
+
+#[pyfunction]
+pub fn get_all_json(py: Python<'_>, text: &str) -> PyResult<Vec<PyObject>> {
+    all_json(text).iter().map(|v| json_to_py(py, v)).collect()
+}
+
+static YAML_FENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)```[ \t]*ya?ml[^\n]*\n(.*?)(?:\n[ \t]*```|$)").unwrap());
+
+// YAML 不允许用制表符缩进, 行首的每个制表符替换为两个空格
+fn expand_leading_tabs(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+            let (head, rest) = line.split_at(indent);
+            format!("{}{}", head.replace('\t', "  "), rest)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// 只去除首尾的开场白与结束语段落, 不做 JSON 提取, 避免破坏 YAML 中的行内列表与键
+fn strip_prose_paragraphs(text: &str) -> String {
+    let mut paragraphs: Vec<&str> = text
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .collect();
+    while paragraphs.len() > 1 && PREAMBLE.is_match(paragraphs[0].lines().next().unwrap_or("")) {
+        debug!("去除开场白: {}", paragraphs[0]);
+        paragraphs.remove(0);
+    }
+    while paragraphs.len() > 1 && EPILOGUE.is_match(paragraphs[paragraphs.len() - 1]) {
+        debug!("去除结束语: {}", paragraphs[paragraphs.len() - 1]);
+        paragraphs.pop();
+    }
+    paragraphs.join("\n\n")
+}
+
+#[pyfunction]
+pub fn get_yaml_from_string(py: Python<'_>, text: &str) -> PyResult<PyObject> {
+    let block = match YAML_FENCE.captures(text) {
+        Some(cap) => cap[1].to_string(),
+        None => {
+            // 没有代码块时先整体解析, 失败后再去除首尾的说明文字
+            if let Ok(value) = serde_yaml::from_str::<Value>(&expand_leading_tabs(text)) {
+                return json_to_py(py, &value);
+            }
+            debug!("未找到 YAML 代码块且全文不是合法的 YAML, 去除首尾的说明文字");
+            strip_prose_paragraphs(text)
+        }
+    };
+    let value: Value = serde_yaml::from_str(&expand_leading_tabs(&block)).map_err(|e| {
+        // 位置为 YAML 块内的字符偏移
+        let position = e
+            .location()
+            .map(|l| block[..l.index().min(block.len())].chars().count());
+        parse_error(py, position, &format!("YAML 解析失败: {}", e))
+    })?;
+    json_to_py(py, &value)
+}
+
+#[pyfunction]
+#[pyo3(signature = (items, threshold=None, config=None))]
+pub fn dedup_items(
+    items: Vec<String>,
+    threshold: Option<f64>,
+    config: Option<CoreConfig>,
+) -> PyResult<(Vec<String>, Vec<(String, String)>)> {
+    let threshold = threshold.unwrap_or_else(|| config.unwrap_or_default().similarity_threshold);
+    // 比较前去除首尾标点并统一全半角、大小写与空白
+    let mut kept: Vec<(String, Vec<char>)> = Vec::new();
+    let mut collapsed = Vec::new();
+    for item in items {
+        let key: Vec<char> =
+            normalize_key(item.trim_matches(|c: char| is_punct(c) || c.is_whitespace()))
+                .chars()
+                .collect();
+        if key.is_empty() {
+            continue;
+        }
+        // 编辑距离相似度, 或一方包含另一方时的长度比, 取较大者
+        let duplicate = kept.iter().find(|(_, other)| {
+            let (short, long) = if key.len() <= other.len() {
+                (&key, other)
+            } else {
+                (other, &key)
+            };
+            let contained = long.windows(short.len()).any(|w| w == short.as_slice());
+            (contained && short.len() as f64 >= threshold * long.len() as f64)
+                || normalized_similarity(&key, other, threshold) >= threshold
+        });
+        match duplicate {
+            Some((first, _)) => collapsed.push((item, first.clone())),
+            None => kept.push((item, key)),
+        }
+    }
+    Ok((kept.into_iter().map(|(item, _)| item).collect(), collapsed))
+}
+
+// 批量解析的单项结果, 在释放 GIL 时生成, 之后再转换为 Python 对象
+enum Parsed {
+    Items(Vec<String>),
+    Json(Vec<Value>),
+    Triples(Vec<(String, String, String)>),
+    KeyValues(BTreeMap<String, String>),
+}
+
+#[pyfunction]
+#[pyo3(signature = (outputs, parser, options=None))]
+pub fn parse_batch(
+    py: Python<'_>,
+    outputs: Vec<String>,
+    parser: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<(PyObject, Vec<String>)>> {
+    let mut format = "auto".to_string();
+    let mut keys: Vec<String> = Vec::new();
+    for (key, value) in options.into_iter().flatten() {
+        match key.extract::<String>()?.as_str() {
+            "format" => format = value.extract()?,
+            "keys" => keys = value.extract()?,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "未知的选项: {other}, 可选 format / keys"
+                )))
+            }
+        }
+    }
+    let field = match parser {
+        "list" | "json" => None,
+        "triples" if triples_by_format("", &format).is_some() => None,
+        "triples" => return Err(unknown_format(&format)),
+        "key_values" if keys.is_empty() => {
+            return Err(PyValueError::new_err("key_values 解析需要 keys 选项"))
+        }
+        "key_values" => Some(key_value_pattern(&keys)?),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "未知的解析器: {parser}, 可选 list / json / triples / key_values"
+            )))
+        }
+    };
+
+    let parsed: Vec<(Parsed, Vec<String>)> = py.allow_threads(|| {
+        install(|| {
+            outputs
+                .par_iter()
+                .map(|text| match parser {
+                    "list" => {
+                        let items = parse_enumerated(text).unwrap_or_default();
+                        let errors = if items.is_empty() {
+                            vec!["未找到列表项".to_string()]
+                        } else {
+                            Vec::new()
+                        };
+                        (Parsed::Items(items), errors)
+                    }
+                    "json" => {
+                        let values = all_json(text);
+                        let errors = if values.is_empty() {
+                            vec!["未找到 JSON".to_string()]
+                        } else {
+                            Vec::new()
+                        };
+                        (Parsed::Json(values), errors)
+                    }
+                    "triples" => {
+                        let (triples, mut errors) = triples_by_format(text, &format)
+                            .map(|(_, parsed)| parsed)
+                            .unwrap_or_default();
+                        if triples.is_empty() && errors.is_empty() {
+                            errors.push("未找到三元组".to_string());
+                        }
+                        (Parsed::Triples(triples), errors)
+                    }
+                    _ => {
+                        let values = scan_key_values(text, field.as_ref().unwrap());
+                        let errors = keys
+                            .iter()
+                            .filter(|k| !values.contains_key(k.trim()))
+                            .map(|k| format!("缺少字段: {}", k.trim()))
+                            .collect();
+                        (Parsed::KeyValues(values), errors)
+                    }
+                })
+                .collect()
+        })
+    });
+    debug!(
+        "按 {} 解析 {} 条输出, 其中 {} 条有错误",
+        parser,
+        parsed.len(),
+        parsed
+            .iter()
+            .filter(|(_, errors)| !errors.is_empty())
+            .count()
+    );
+
+    parsed
+        .into_iter()
+        .map(|(result, errors)| {
+            let result = match result {
+                Parsed::Items(items) => items.into_py(py),
+                Parsed::Json(values) => values
+                    .iter()
+                    .map(|v| json_to_py(py, v))
+                    .collect::<PyResult<Vec<_>>>()?
+                    .into_py(py),
+                Parsed::Triples(triples) => triples.into_py(py),
+                Parsed::KeyValues(values) => values.into_py(py),
+            };
+            Ok((result, errors))
+        })
+        .collect()
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_triples, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_enumerated, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_key_values, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_extraction, m)?)?;
+    m.add_function(wrap_pyfunction!(isolate_structured, m)?)?;
+    m.add_function(wrap_pyfunction!(get_all_json, m)?)?;
+    m.add_function(wrap_pyfunction!(get_yaml_from_string, m)?)?;
+    m.add_function(wrap_pyfunction!(dedup_items, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_batch, m)?)?;
+    Ok(())
+}
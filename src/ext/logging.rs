@@ -0,0 +1,10 @@
+use pyo3::prelude::*;
+use pyo3_log::{Caching, Logger};
+
+// 将 log 记录转发到 Python 的 logging, 目标 "_core::ext::llm" 对应 logger "_core.ext.llm";
+// 缓存各 logger 及其级别, 未启用的级别不再获取 GIL
+pub fn init(py: Python<'_>) -> PyResult<()> {
+    // 重复导入时 logger 已设置, 忽略错误
+    let _ = Logger::new(py, Caching::LoggersAndLevels)?.install();
+    Ok(())
+}
@@ -0,0 +1,265 @@
+use std::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use regex::Regex;
+
+// 按顺序执行的 (模式, 替换) 步骤
+static STRIP_STEPS: Lazy<Vec<(Regex, &str)>> = Lazy::new(|| {
+    [
+        // 去掉围栏代码块, 代码另行提取
+        (r"(?ms)^[ \t]*(```|~~~).*?^[ \t]*(```|~~~)[ \t]*$", ""),
+        (r"(?s)<!--.*?-->", ""),
+        (r"!\[[^\]]*\]\([^)]*\)|!\[[^\]]*\]\[[^\]]*\]", ""),
+        (r"\[([^\]]*)\]\([^)]*\)|\[([^\]]*)\]\[[^\]]*\]", "$1$2"),
+        (r"(?m)^[ \t]*\[[^\]]+\]:[ \t]*\S+.*$", ""),
+        (r"<((?:https?|ftp)://[^>\s]+|[^>\s@]+@[^>\s@]+)>", "$1"),
+        (r"</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>", ""),
+        (r"(?m)^[ \t]{0,3}#{1,6}[ \t]+(.*?)[ \t]*#*[ \t]*$", "$1"),
+        // 水平线须在列表标记之前处理, 否则 "- - -" 会被当作列表
+        (r"(?m)^[ \t]*([-*_][ \t]*){3,}$", ""),
+        (r"(?m)^[ \t]*(=+|-+)[ \t]*$", ""),
+        (r"(?m)^[ \t]*>[ \t]?", ""),
+        (r"(?m)^([ \t]*)[-*+][ \t]+", "$1"),
+        (r"`+([^`]+)`+", "$1"),
+        (r"\*\*(.+?)\*\*|__(.+?)__", "$1$2"),
+        (
+            r"\*([^*\s](?:[^*]*[^*\s])?)\*|\b_([^_\s](?:[^_]*[^_\s])?)_\b",
+            "$1$2",
+        ),
+        (r"~~(.+?)~~", "$1"),
+        (r"\n{3,}", "\n\n"),
+    ]
+    .into_iter()
+    .map(|(pattern, rep)| (Regex::new(pattern).unwrap(), rep))
+    .collect()
+});
+
+pub fn warm_up() {
+    Lazy::force(&STRIP_STEPS);
+}
+
+#[pyfunction]
+pub fn markdown_to_text(md: &str) -> PyResult<String> {
+    let mut text = md.to_string();
+    for (pattern, rep) in STRIP_STEPS.iter() {
+        if let Cow::Owned(replaced) = pattern.replace_all(&text, *rep) {
+            text = replaced;
+        }
+    }
+
+    Ok(text.trim().to_string())
+}
+
+// 判断是否为围栏代码块的起止行, 返回围栏字符与长度
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    let c = trimmed.chars().next()?;
+    if c != '`' && c != '~' {
+        return None;
+    }
+    let len = trimmed.chars().take_while(|&x| x == c).count();
+    if len >= 3 {
+        Some((c, len))
+    } else {
+        None
+    }
+}
+
+// ATX 标题, 返回级别与标题文本
+fn atx_heading(line: &str) -> Option<(usize, String)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, title.to_string()))
+}
+
+#[pyfunction]
+pub fn split_markdown_sections(md: &str, max_level: usize) -> PyResult<Vec<(Vec<String>, String)>> {
+    let mut result = Vec::new();
+    let mut path: Vec<(usize, String)> = Vec::new();
+    let mut body = String::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in md.lines() {
+        if let Some((c, len)) = fence_marker(line) {
+            match fence {
+                None => fence = Some((c, len)),
+                Some((open_c, open_len)) if open_c == c && len >= open_len => fence = None,
+                _ => {}
+            }
+        } else if fence.is_none() {
+            if let Some((level, title)) = atx_heading(line) {
+                if level <= max_level {
+                    let trimmed = body.trim();
+                    if !trimmed.is_empty() {
+                        let heading_path = path.iter().map(|(_, t)| t.clone()).collect();
+                        result.push((heading_path, trimmed.to_string()));
+                    }
+                    body.clear();
+                    // 弹出同级及更深的标题
+                    while path.last().is_some_and(|(l, _)| *l >= level) {
+                        path.pop();
+                    }
+                    path.push((level, title));
+                    continue;
+                }
+            }
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    let trimmed = body.trim();
+    if !trimmed.is_empty() {
+        let heading_path = path.iter().map(|(_, t)| t.clone()).collect();
+        result.push((heading_path, trimmed.to_string()));
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn get_markdown_code_blocks(md: &str) -> PyResult<Vec<(String, String, usize)>> {
+    let mut result = Vec::new();
+    // 当前打开的代码块: 围栏字符, 围栏长度, 语言, 起始字符偏移
+    let mut open: Option<(char, usize, String, usize)> = None;
+    let mut code = String::new();
+    let mut offset = 0;
+
+    for line in md.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        match (&open, fence_marker(content)) {
+            (None, Some((c, len))) => {
+                let info = content.trim_start()[len * c.len_utf8()..].trim();
+                // 反引号围栏的信息串中不允许出现反引号
+                if c != '`' || !info.contains('`') {
+                    let language = info.split_whitespace().next().unwrap_or("").to_string();
+                    open = Some((c, len, language, offset));
+                    code.clear();
+                }
+            }
+            (Some((open_c, open_len, _, _)), Some((c, len)))
+                if *open_c == c
+                    && len >= *open_len
+                    && content.trim()[len * c.len_utf8()..].is_empty() =>
+            {
+                let (_, _, language, start) = open.take().unwrap();
+                result.push((language, code.trim_end_matches('\n').to_string(), start));
+            }
+            (Some(_), _) => code.push_str(line),
+            (None, None) => {}
+        }
+        offset += line.chars().count();
+    }
+
+    // 未闭合的代码块延伸至文末
+    if let Some((_, _, language, start)) = open {
+        result.push((language, code.trim_end_matches('\n').to_string(), start));
+    }
+
+    Ok(result)
+}
+
+// 列表项, 返回缩进宽度与条目文本
+fn list_item(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line[..line.len() - trimmed.len()]
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = if digits > 0 && digits <= 9 {
+        let rest = trimmed[digits..].strip_prefix(['.', ')'])?;
+        // 单独一行的 "2024." 或 "3)" 多为正文, 不视为空的有序列表项
+        if rest.trim().is_empty() {
+            return None;
+        }
+        rest
+    } else {
+        trimmed.strip_prefix(['-', '*', '+'])?
+    };
+    if rest.is_empty() {
+        return Some((indent, ""));
+    }
+    if !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((indent, rest.trim()))
+}
+
+#[pyfunction]
+pub fn parse_markdown_lists(md: &str) -> PyResult<Vec<(String, usize, Vec<String>)>> {
+    let mut result: Vec<(String, usize, Vec<String>)> = Vec::new();
+    // 祖先列表项: 缩进宽度, 条目在 result 中的下标
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in md.lines() {
+        if let Some((c, len)) = fence_marker(line) {
+            match fence {
+                None => fence = Some((c, len)),
+                Some((open_c, open_len)) if open_c == c && len >= open_len => fence = None,
+                _ => {}
+            }
+            continue;
+        }
+        if fence.is_some() || line.trim().is_empty() {
+            continue;
+        }
+        // 分隔线不是列表项
+        if line
+            .trim()
+            .chars()
+            .all(|c| c == '-' || c == '*' || c == ' ')
+            && line.trim().len() >= 3
+        {
+            stack.clear();
+            continue;
+        }
+
+        if let Some((indent, text)) = list_item(line) {
+            while stack.last().is_some_and(|(i, _)| *i >= indent) {
+                stack.pop();
+            }
+            let parents = stack
+                .iter()
+                .map(|(_, idx)| result[*idx].0.clone())
+                .collect();
+            result.push((text.to_string(), stack.len(), parents));
+            stack.push((indent, result.len() - 1));
+        } else if let Some(&(indent, idx)) = stack.last() {
+            let line_indent = line.len() - line.trim_start().len();
+            if line_indent > indent {
+                // 缩进的续行并入上一条目
+                let item = &mut result[idx].0;
+                if !item.is_empty() {
+                    item.push(' ');
+                }
+                item.push_str(line.trim());
+            } else {
+                stack.clear();
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(markdown_to_text, m)?)?;
+    m.add_function(wrap_pyfunction!(split_markdown_sections, m)?)?;
+    m.add_function(wrap_pyfunction!(get_markdown_code_blocks, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_markdown_lists, m)?)?;
+    Ok(())
+}
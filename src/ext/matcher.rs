@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashMap};
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use regex::{Regex, RegexSet};
+
+use super::clean::to_half_width;
+use super::stats::is_punct;
+
+// 字节偏移转字符偏移, offsets 需递增
+pub fn byte_to_char_offsets(text: &str, offsets: &[usize]) -> Vec<usize> {
+    let mut result = Vec::with_capacity(offsets.len());
+    let mut chars = 0;
+    let mut last = 0;
+    for &offset in offsets {
+        chars += text[last..offset].chars().count();
+        last = offset;
+        result.push(chars);
+    }
+    result
+}
+
+#[pyclass]
+pub struct TermMatcher {
+    terms: Vec<String>,
+    automaton: AhoCorasick,
+}
+
+#[pymethods]
+impl TermMatcher {
+    #[new]
+    #[pyo3(signature = (terms, ignore_case = false))]
+    fn new(terms: Vec<String>, ignore_case: bool) -> PyResult<Self> {
+        let terms: Vec<String> = terms.into_iter().filter(|t| !t.is_empty()).collect();
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .ascii_case_insensitive(ignore_case)
+            .build(&terms)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(TermMatcher { terms, automaton })
+    }
+
+    fn find_all(&self, text: &str) -> PyResult<Vec<(String, usize, usize)>> {
+        let matches: Vec<_> = self.automaton.find_iter(text).collect();
+        let mut offsets = Vec::with_capacity(matches.len() * 2);
+        for m in &matches {
+            offsets.push(m.start());
+            offsets.push(m.end());
+        }
+        let offsets = byte_to_char_offsets(text, &offsets);
+
+        Ok(matches
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                (
+                    self.terms[m.pattern().as_usize()].clone(),
+                    offsets[2 * i],
+                    offsets[2 * i + 1],
+                )
+            })
+            .collect())
+    }
+
+    fn __len__(&self) -> usize {
+        self.terms.len()
+    }
+}
+
+// 对齐用的字符归一化: 忽略空白与标点, 全角转半角, 忽略大小写
+fn fold_char(c: char) -> Option<char> {
+    let c = to_half_width(c);
+    if c.is_whitespace() || is_punct(c) {
+        return None;
+    }
+    Some(c.to_lowercase().next().unwrap_or(c))
+}
+
+#[pyfunction]
+pub fn locate_mentions(text: &str, entities: Vec<String>) -> PyResult<Vec<Vec<(usize, usize)>>> {
+    // 归一化文本及其每个字符在原文中的字符下标
+    let mut folded = String::new();
+    let mut positions = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        if let Some(f) = fold_char(c) {
+            folded.push(f);
+            positions.push(i);
+        }
+    }
+    // 归一化文本的字节偏移到字符下标
+    let mut byte_index = vec![0; folded.len() + 1];
+    for (i, (b, _)) in folded.char_indices().enumerate() {
+        byte_index[b] = i;
+    }
+
+    let mut result = Vec::with_capacity(entities.len());
+    for entity in &entities {
+        let pattern: String = entity.chars().filter_map(fold_char).collect();
+        let mut spans = Vec::new();
+        if !pattern.is_empty() {
+            let len = pattern.chars().count();
+            for (b, _) in folded.match_indices(&pattern) {
+                let start = byte_index[b];
+                spans.push((positions[start], positions[start + len - 1] + 1));
+            }
+        }
+        result.push(spans);
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn scan_patterns(
+    text: &str,
+    patterns: HashMap<String, String>,
+) -> PyResult<BTreeMap<String, Vec<(usize, usize, String)>>> {
+    let mut names: Vec<&String> = patterns.keys().collect();
+    names.sort();
+    let sources: Vec<&str> = names.iter().map(|n| patterns[*n].as_str()).collect();
+    let mut regexes = Vec::with_capacity(sources.len());
+    for (name, source) in names.iter().zip(&sources) {
+        let regex = Regex::new(source)
+            .map_err(|e| PyValueError::new_err(format!("模式 {} 无法编译: {}", name, e)))?;
+        regexes.push(regex);
+    }
+    let set = RegexSet::new(&sources).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    let mut result = BTreeMap::new();
+    // 先用 RegexSet 一次扫描筛出命中的模式, 再逐个取出匹配位置
+    for i in set.matches(text).iter() {
+        let found: Vec<_> = regexes[i].find_iter(text).collect();
+        // 匹配互不重叠且递增, 起止偏移序列单调
+        let offsets: Vec<usize> = found.iter().flat_map(|m| [m.start(), m.end()]).collect();
+        let chars = byte_to_char_offsets(text, &offsets);
+        let spans = found
+            .iter()
+            .enumerate()
+            .map(|(k, m)| (chars[2 * k], chars[2 * k + 1], m.as_str().to_string()))
+            .collect();
+        result.insert(names[i].clone(), spans);
+    }
+
+    Ok(result)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<TermMatcher>()?;
+    m.add_function(wrap_pyfunction!(locate_mentions, m)?)?;
+    m.add_function(wrap_pyfunction!(scan_patterns, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::fs::File;
+
+use memmap2::Mmap;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::config::CoreConfig;
+use super::heading::parse_cn_heading;
+use super::markdown::split_markdown_sections;
+
+// 以只读方式映射文件, 内容由操作系统按需换页, 不复制到 Python 字符串
+fn map_file(path: &str) -> PyResult<Mmap> {
+    let file =
+        File::open(path).map_err(|e| PyValueError::new_err(format!("无法读取 {}: {}", path, e)))?;
+    // 映射期间文件被其他进程截断会导致访问越界, 这里假定输入文件在处理期间不被修改
+    unsafe { Mmap::map(&file) }
+        .map_err(|e| PyValueError::new_err(format!("无法映射 {}: {}", path, e)))
+}
+
+fn as_text<'a>(path: &str, mmap: &'a Mmap) -> PyResult<&'a str> {
+    std::str::from_utf8(mmap)
+        .map_err(|e| PyValueError::new_err(format!("{} 不是合法的 UTF-8 文本: {}", path, e)))
+}
+
+// 将映射文件的各行依次交给分块函数, 处理期间释放 GIL
+fn chunk_lines(
+    py: Python<'_>,
+    path: &str,
+    chunker: fn(
+        Option<i32>,
+        Option<CoreConfig>,
+        Box<crate::StrSource<'_>>,
+    ) -> PyResult<Vec<String>>,
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    let mmap = map_file(path)?;
+    let text = as_text(path, &mmap)?;
+    py.allow_threads(|| {
+        chunker(
+            n,
+            config,
+            Box::new(|f| {
+                text.lines().for_each(f);
+                Ok(())
+            }),
+        )
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, n=None, config=None))]
+pub fn optimize_length_file(
+    py: Python<'_>,
+    path: &str,
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    chunk_lines(py, path, crate::optimize_length_with, n, config)
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, n=None, config=None))]
+pub fn merge_file(
+    py: Python<'_>,
+    path: &str,
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    chunk_lines(py, path, crate::merge_with, n, config)
+}
+
+#[pyfunction]
+pub fn split_markdown_sections_file(
+    py: Python<'_>,
+    path: &str,
+    max_level: usize,
+) -> PyResult<Vec<(Vec<String>, String)>> {
+    let mmap = map_file(path)?;
+    let text = as_text(path, &mmap)?;
+    py.allow_threads(|| split_markdown_sections(text, max_level))
+}
+
+#[pyfunction]
+pub fn parse_cn_headings_file(
+    py: Python<'_>,
+    path: &str,
+) -> PyResult<Vec<(usize, usize, u64, String)>> {
+    let mmap = map_file(path)?;
+    let text = as_text(path, &mmap)?;
+    py.allow_threads(|| {
+        let mut result = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if let Some((level, number, title)) = parse_cn_heading(line)? {
+                result.push((i, level, number, title));
+            }
+        }
+        Ok(result)
+    })
+}
+
+pub fn register_chunk(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(optimize_length_file, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_file, m)?)?;
+    Ok(())
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(split_markdown_sections_file, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_cn_headings_file, m)?)?;
+    Ok(())
+}
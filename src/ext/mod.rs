@@ -0,0 +1,140 @@
+use pyo3::exceptions::{PyAttributeError, PyDeprecationWarning};
+use pyo3::prelude::*;
+
+pub mod alias;
+pub mod arrow;
+pub mod cache;
+pub mod clean;
+pub mod config;
+pub mod dedup;
+pub mod errors;
+pub mod export;
+pub mod extract;
+pub mod graph;
+pub mod hashing;
+pub mod heading;
+pub mod html;
+pub mod json;
+pub mod kg;
+pub mod latex;
+pub mod length;
+pub mod llm;
+pub mod logging;
+pub mod markdown;
+pub mod matcher;
+pub mod mmap;
+pub mod numeral;
+pub mod phonetic;
+pub mod pipeline;
+pub mod search;
+pub mod segment;
+pub mod sentence;
+pub mod similarity;
+pub mod stats;
+pub mod stopwords;
+pub mod stubs;
+pub mod threads;
+pub mod tokens;
+pub mod triples;
+pub mod vector;
+pub mod writer;
+pub mod zh;
+
+// 预先编译各模块的正则与查找表, 避免首次调用时的延迟
+#[pyfunction]
+pub fn warm_up() {
+    markdown::warm_up();
+    heading::warm_up();
+    clean::warm_up();
+    zh::warm_up();
+    extract::warm_up();
+    segment::warm_up();
+    stopwords::warm_up();
+    stats::warm_up();
+}
+
+// 按用途划分的子模块, 其中的函数在顶层保留为已弃用的别名
+pub const SUBMODULES: [&str; 5] = ["chunk", "layout", "latex", "kg", "parse"];
+
+// 创建 (或取出已创建的) 子模块并登记到 sys.modules, 以支持 from course_graph._core.kg import ...
+pub fn submodule<'py>(m: &Bound<'py, PyModule>, name: &str) -> PyResult<Bound<'py, PyModule>> {
+    if let Ok(sub) = m.getattr(name) {
+        return Ok(sub.downcast_into()?);
+    }
+    let py = m.py();
+    let full_name = format!("{}.{}", m.name()?, name);
+    let sub = PyModule::new_bound(py, &full_name)?;
+    m.add(name, &sub)?;
+    py.import_bound("sys")?
+        .getattr("modules")?
+        .set_item(full_name, &sub)?;
+    Ok(sub)
+}
+
+// 模块级 __getattr__ (PEP 562): 旧的顶层名称转发到子模块并给出弃用警告
+#[pyfunction]
+#[pyo3(pass_module)]
+fn __getattr__(m: &Bound<'_, PyModule>, name: &str) -> PyResult<PyObject> {
+    let py = m.py();
+    for sub in SUBMODULES {
+        if let Ok(attr) = m.getattr(sub)?.getattr(name) {
+            PyErr::warn_bound(
+                py,
+                &py.get_type_bound::<PyDeprecationWarning>(),
+                &format!("_core.{name} 已弃用, 请使用 _core.{sub}.{name}"),
+                2,
+            )?;
+            return Ok(attr.unbind());
+        }
+    }
+    Err(PyAttributeError::new_err(format!(
+        "module '{}' has no attribute '{}'",
+        m.name()?,
+        name
+    )))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    logging::init(m.py())?;
+    errors::register(m)?;
+    config::register(m)?;
+    markdown::register(m)?;
+    html::register(m)?;
+    heading::register(m)?;
+    numeral::register(m)?;
+    clean::register(m)?;
+    zh::register(m)?;
+    stats::register(m)?;
+    extract::register(m)?;
+    matcher::register(m)?;
+    similarity::register(m)?;
+    dedup::register(m)?;
+    vector::register(m)?;
+    search::register(m)?;
+    segment::register(m)?;
+    stopwords::register(m)?;
+    phonetic::register(m)?;
+    hashing::register(m)?;
+    cache::register(m)?;
+    mmap::register(m)?;
+    length::register(m)?;
+    pipeline::register(m)?;
+    sentence::register(m)?;
+    tokens::register(m)?;
+
+    let kg = submodule(m, "kg")?;
+    triples::register(&kg)?;
+    alias::register(&kg)?;
+    kg::register(&kg)?;
+    export::register(&kg)?;
+    graph::register(&kg)?;
+
+    llm::register(&submodule(m, "parse")?)?;
+    latex::register(&submodule(m, "latex")?)?;
+
+    stubs::register(m)?;
+    threads::register(m)?;
+    m.add_function(wrap_pyfunction!(warm_up, m)?)?;
+    m.add_function(wrap_pyfunction!(__getattr__, m)?)?;
+    Ok(())
+}
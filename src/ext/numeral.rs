@@ -0,0 +1,176 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const DIGITS: [char; 10] = ['零', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+fn digit_value(c: char) -> Option<u64> {
+    match c {
+        '零' | '〇' => Some(0),
+        '一' | '壹' => Some(1),
+        '二' | '两' | '贰' => Some(2),
+        '三' | '叁' => Some(3),
+        '四' | '肆' => Some(4),
+        '五' | '伍' => Some(5),
+        '六' | '陆' => Some(6),
+        '七' | '柒' => Some(7),
+        '八' | '捌' => Some(8),
+        '九' | '玖' => Some(9),
+        '0'..='9' => c.to_digit(10).map(u64::from),
+        '０'..='９' => Some(c as u64 - '０' as u64),
+        _ => None,
+    }
+}
+
+fn unit_value(c: char) -> Option<u64> {
+    match c {
+        '十' | '拾' => Some(10),
+        '百' | '佰' => Some(100),
+        '千' | '仟' => Some(1000),
+        _ => None,
+    }
+}
+
+pub fn is_cn_numeral(c: char) -> bool {
+    (digit_value(c).is_some() && !c.is_ascii_digit() && !('０'..='９').contains(&c))
+        || unit_value(c).is_some()
+        || c == '万'
+        || c == '亿'
+}
+
+// 中文数字 (可混合阿拉伯数字) 转整数, 支持 零/两/十百千万亿, 以及 "二〇二四" 这样的逐位写法
+pub fn parse_cn_num(s: &str) -> Option<u64> {
+    if s.is_empty() {
+        return None;
+    }
+    // 没有单位时按位拼接
+    if s.chars().all(|c| digit_value(c).is_some()) {
+        return s.chars().try_fold(0u64, |acc, c| {
+            acc.checked_mul(10)?.checked_add(digit_value(c)?)
+        });
+    }
+
+    // 已累计的亿级部分, 万级部分, 万以下部分, 尚未乘单位的数字
+    let mut yi = 0u64;
+    let mut wan = 0u64;
+    let mut section = 0u64;
+    let mut digit: Option<u64> = None;
+    // "三万五" 中末位数字承接前一单位的下一级
+    let mut last_unit = 1u64;
+    let mut after_unit = false;
+    let mut scale = 1u64;
+
+    for c in s.chars() {
+        let is_unit = unit_value(c).is_some() || c == '万' || c == '亿';
+        if let Some(v) = digit_value(c) {
+            scale = if after_unit { last_unit / 10 } else { 1 };
+            // "零" 只作占位, 不参与计算
+            digit = match (digit, v) {
+                (_, 0) if !c.is_ascii_digit() => None,
+                (Some(d), _) if c.is_ascii_digit() || ('０'..='９').contains(&c) => {
+                    Some(d.checked_mul(10)?.checked_add(v)?)
+                }
+                _ => Some(v),
+            };
+        } else if let Some(unit) = unit_value(c) {
+            last_unit = unit;
+            // "十二" 省略了前面的 "一"
+            section = section.checked_add(digit.take().unwrap_or(1).checked_mul(unit)?)?;
+        } else if c == '万' {
+            last_unit = 10_000;
+            let value = section + digit.take().unwrap_or(0);
+            wan = wan.checked_add(value.max(1).checked_mul(10_000)?)?;
+            section = 0;
+        } else if c == '亿' {
+            last_unit = 100_000_000;
+            let value = wan + section + digit.take().unwrap_or(0);
+            yi = yi.checked_add(value)?.max(1).checked_mul(100_000_000)?;
+            wan = 0;
+            section = 0;
+        } else {
+            return None;
+        }
+        after_unit = is_unit;
+    }
+
+    yi.checked_add(wan)?
+        .checked_add(section)?
+        .checked_add(digit.unwrap_or(0).checked_mul(scale.max(1))?)
+}
+
+pub fn is_number_char(c: char) -> bool {
+    digit_value(c).is_some() || is_cn_numeral(c)
+}
+
+// 四位以内的数字转中文
+fn section_to_cn(n: u64, leading: bool) -> String {
+    let mut result = String::new();
+    let units = ["千", "百", "十", ""];
+    let mut zero = false;
+    for (i, unit) in units.iter().enumerate() {
+        let d = n / 10u64.pow(3 - i as u32) % 10;
+        if d == 0 {
+            zero = !result.is_empty();
+            continue;
+        }
+        if zero {
+            result.push('零');
+            zero = false;
+        }
+        // 首位的 "一十" 读作 "十"
+        if !(leading && d == 1 && *unit == "十" && result.is_empty()) {
+            result.push(DIGITS[d as usize]);
+        }
+        result.push_str(unit);
+    }
+    result
+}
+
+pub fn to_cn_num(n: u64) -> String {
+    if n == 0 {
+        return DIGITS[0].to_string();
+    }
+    let units = ["", "万", "亿", "万亿"];
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push(rest % 10_000);
+        rest /= 10_000;
+    }
+
+    let mut result = String::new();
+    let mut zero = false;
+    for (i, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            zero = !result.is_empty();
+            continue;
+        }
+        // 前一节非空且本节不满千时补 "零"
+        if zero || (!result.is_empty() && group < 1000) {
+            result.push('零');
+        }
+        zero = false;
+        result.push_str(&section_to_cn(group, result.is_empty()));
+        result.push_str(units[i]);
+    }
+    result
+}
+
+#[pyfunction]
+pub fn cn_num_to_int(s: &str) -> PyResult<u64> {
+    parse_cn_num(s.trim())
+        .ok_or_else(|| PyValueError::new_err(format!("无法解析的中文数字: {}", s)))
+}
+
+#[pyfunction]
+pub fn int_to_cn_num(n: u64) -> PyResult<String> {
+    if n >= 10u64.pow(16) {
+        return Err(PyValueError::new_err(format!("数字过大: {}", n)));
+    }
+    Ok(to_cn_num(n))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(cn_num_to_int, m)?)?;
+    m.add_function(wrap_pyfunction!(int_to_cn_num, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use pinyin::{ToPinyin, ToPinyinMulti};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::clean::to_half_width;
+use super::similarity::normalized_similarity;
+
+// 比较单元: 汉字取其全部读音, 其他字符按半角小写比较
+enum Sound {
+    Readings(Vec<&'static str>),
+    Char(char),
+}
+
+// 多音字只要有一个读音相同即视为同音
+impl PartialEq for Sound {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Sound::Readings(a), Sound::Readings(b)) => a.iter().any(|r| b.contains(r)),
+            (Sound::Char(a), Sound::Char(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn sounds(text: &str) -> Vec<Sound> {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_pinyin_multi() {
+            Some(multi) => {
+                let mut readings: Vec<&'static str> =
+                    multi.into_iter().map(|p| p.plain()).collect();
+                readings.dedup();
+                Sound::Readings(readings)
+            }
+            None => Sound::Char(to_half_width(c).to_ascii_lowercase()),
+        })
+        .collect()
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, style="plain"))]
+pub fn to_pinyin(text: &str, style: &str) -> PyResult<Vec<String>> {
+    let convert: fn(pinyin::Pinyin) -> &'static str = match style {
+        "plain" => pinyin::Pinyin::plain,
+        "tone" => pinyin::Pinyin::with_tone,
+        "tone_num" => pinyin::Pinyin::with_tone_num_end,
+        "first_letter" => pinyin::Pinyin::first_letter,
+        _ => return Err(PyValueError::new_err(format!("未知的拼音风格: {}", style))),
+    };
+    Ok(text
+        .chars()
+        .zip(text.to_pinyin())
+        .map(|(c, p)| match p {
+            Some(p) => convert(p).to_string(),
+            None => c.to_string(),
+        })
+        .collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b, min_similarity=0.0))]
+pub fn pinyin_similarity(a: &str, b: &str, min_similarity: f64) -> PyResult<f64> {
+    Ok(normalized_similarity(
+        &sounds(a),
+        &sounds(b),
+        min_similarity,
+    ))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(to_pinyin, m)?)?;
+    m.add_function(wrap_pyfunction!(pinyin_similarity, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::clean::{
+    clean_text, handle_footnotes, join_pdf_lines, normalize_cjk_spacing, normalize_punct,
+};
+use super::config::CoreConfig;
+use super::html::html_to_text;
+use super::markdown::markdown_to_text;
+use super::threads::install;
+use super::zh::t2s;
+
+const STEPS: [&str; 11] = [
+    "clean_text",
+    "normalize_punct",
+    "normalize_cjk_spacing",
+    "t2s",
+    "strip_footnotes",
+    "markdown_to_text",
+    "html_to_text",
+    "split_lines",
+    "join_pdf_lines",
+    "optimize_length",
+    "merge",
+];
+
+// 逐条文本独立处理的步骤, 参数均取各函数的默认值
+fn map_step(step: &str, text: &str) -> PyResult<String> {
+    match step {
+        "clean_text" => clean_text(text, true, true, true, true),
+        "normalize_punct" => normalize_punct(text, "zh"),
+        "normalize_cjk_spacing" => normalize_cjk_spacing(text),
+        "t2s" => t2s(text),
+        "strip_footnotes" => Ok(handle_footnotes(text, "strip")?.0),
+        "markdown_to_text" => markdown_to_text(text),
+        "html_to_text" => html_to_text(text),
+        _ => unreachable!(),
+    }
+}
+
+fn source(texts: &[String]) -> Box<crate::StrSource<'_>> {
+    Box::new(move |f| {
+        texts.iter().for_each(|t| f(t));
+        Ok(())
+    })
+}
+
+fn run_step(step: &str, texts: Vec<String>, config: &CoreConfig) -> PyResult<Vec<String>> {
+    match step {
+        // 按行拆分, 去掉首尾空白与空行
+        "split_lines" => Ok(texts
+            .iter()
+            .flat_map(|t| t.lines().map(str::trim).filter(|l| !l.is_empty()))
+            .map(str::to_string)
+            .collect()),
+        "join_pdf_lines" => join_pdf_lines(texts),
+        "optimize_length" => {
+            crate::optimize_length_with(None, Some(config.clone()), source(&texts))
+        }
+        "merge" => crate::merge_with(None, Some(config.clone()), source(&texts)),
+        _ => install(|| texts.par_iter().map(|t| map_step(step, t)).collect()),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (texts, steps, config=None))]
+pub fn run_pipeline(
+    py: Python<'_>,
+    texts: Vec<String>,
+    steps: Vec<String>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    // 先检查全部步骤, 避免执行到一半才报错
+    if let Some(step) = steps.iter().find(|s| !STEPS.contains(&s.as_str())) {
+        return Err(PyValueError::new_err(format!(
+            "未知的步骤: {}, 可选 {}",
+            step,
+            STEPS.join(" / ")
+        )));
+    }
+    let config = config.unwrap_or_default();
+    py.allow_threads(|| {
+        steps
+            .iter()
+            .try_fold(texts, |texts, step| run_step(step, texts, &config))
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(run_pipeline, m)?)?;
+    Ok(())
+}
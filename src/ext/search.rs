@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::clean::to_half_width;
+use super::segment::cut;
+use super::stats::is_ideograph;
+use super::stopwords::is_stopword;
+
+// 将连续的汉字切分为二元组, 单字成词
+fn flush_run(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+    if run.len() == 1 {
+        tokens.push(run[0].to_string());
+    } else {
+        tokens.extend(run.windows(2).map(|w| w.iter().collect()));
+    }
+    run.clear();
+}
+
+// 中文按字符二元组切分, 拉丁字母与数字按词切分并转小写
+pub fn bigram_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut run: Vec<char> = Vec::new();
+    let mut word = String::new();
+
+    for c in text.chars().map(to_half_width) {
+        if c.is_alphanumeric() && !is_ideograph(c) {
+            flush_run(&mut run, &mut tokens);
+            word.extend(c.to_lowercase());
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if is_ideograph(c) {
+            run.push(c);
+        } else {
+            flush_run(&mut run, &mut tokens);
+        }
+    }
+    flush_run(&mut run, &mut tokens);
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+#[pyclass]
+pub struct Bm25Index {
+    k1: f64,
+    b: f64,
+    // 词项到 (文档下标, 词频) 倒排表
+    postings: HashMap<String, Vec<(usize, u32)>>,
+    doc_lens: Vec<usize>,
+    total_len: usize,
+}
+
+#[pymethods]
+impl Bm25Index {
+    #[new]
+    #[pyo3(signature = (k1 = 1.5, b = 0.75))]
+    fn new(k1: f64, b: f64) -> PyResult<Self> {
+        if k1 < 0.0 || !(0.0..=1.0).contains(&b) {
+            return Err(PyValueError::new_err(format!(
+                "参数不合法: k1 = {}, b = {}, 要求 k1 >= 0 且 0 <= b <= 1",
+                k1, b
+            )));
+        }
+        Ok(Bm25Index {
+            k1,
+            b,
+            postings: HashMap::new(),
+            doc_lens: Vec::new(),
+            total_len: 0,
+        })
+    }
+
+    fn add(&mut self, docs: Vec<String>) -> PyResult<()> {
+        for doc in &docs {
+            let index = self.doc_lens.len();
+            let tokens = bigram_tokens(doc);
+            let mut tf: HashMap<String, u32> = HashMap::new();
+            for token in &tokens {
+                *tf.entry(token.clone()).or_default() += 1;
+            }
+            for (token, count) in tf {
+                self.postings.entry(token).or_default().push((index, count));
+            }
+            self.doc_lens.push(tokens.len());
+            self.total_len += tokens.len();
+        }
+        Ok(())
+    }
+
+    #[pyo3(signature = (query, k = 10))]
+    fn search(&self, query: &str, k: usize) -> PyResult<Vec<(usize, f64)>> {
+        let n = self.doc_lens.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let avg_len = (self.total_len as f64 / n as f64).max(1.0);
+        let mut terms = bigram_tokens(query);
+        terms.sort();
+        terms.dedup();
+
+        let mut scores = vec![0.0; n];
+        let mut hit = vec![false; n];
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let df = postings.len() as f64;
+            let idf = ((n as f64 - df + 0.5) / (df + 0.5) + 1.0).ln();
+            for &(doc, tf) in postings {
+                let tf = tf as f64;
+                let norm = self.k1 * (1.0 - self.b + self.b * self.doc_lens[doc] as f64 / avg_len);
+                scores[doc] += idf * tf * (self.k1 + 1.0) / (tf + norm);
+                hit[doc] = true;
+            }
+        }
+
+        // 只返回至少命中一个词项的文档
+        let mut result: Vec<(usize, f64)> =
+            (0..n).filter(|&d| hit[d]).map(|d| (d, scores[d])).collect();
+        result.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        result.truncate(k);
+
+        Ok(result)
+    }
+
+    fn __len__(&self) -> usize {
+        self.doc_lens.len()
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (docs, top_k=10, tokenizer="bigram"))]
+pub fn extract_keywords(
+    py: Python<'_>,
+    docs: Vec<String>,
+    top_k: usize,
+    tokenizer: &str,
+) -> PyResult<Vec<Vec<(String, f64)>>> {
+    py.allow_threads(|| {
+        let mut tokenized: Vec<Vec<String>> = Vec::with_capacity(docs.len());
+        for doc in &docs {
+            let tokens = match tokenizer {
+                "bigram" => bigram_tokens(doc),
+                "jieba" => cut(doc, "default", true)?,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "未知的切分方式: {}",
+                        tokenizer
+                    )))
+                }
+            };
+            // 单字、纯数字、标点与停用词不作为关键词
+            tokenized.push(
+                tokens
+                    .into_iter()
+                    .filter(|t| {
+                        t.chars().count() > 1
+                            && !t.chars().all(|c| c.is_ascii_digit())
+                            && t.chars().any(char::is_alphanumeric)
+                            && !is_stopword(t)
+                    })
+                    .collect(),
+            );
+        }
+
+        let mut df: HashMap<&str, usize> = HashMap::new();
+        for tokens in &tokenized {
+            let mut seen: Vec<&str> = tokens.iter().map(String::as_str).collect();
+            seen.sort_unstable();
+            seen.dedup();
+            for token in seen {
+                *df.entry(token).or_default() += 1;
+            }
+        }
+
+        let n = tokenized.len() as f64;
+        let result = tokenized
+            .iter()
+            .map(|tokens| {
+                let mut tf: HashMap<&str, usize> = HashMap::new();
+                for token in tokens {
+                    *tf.entry(token).or_default() += 1;
+                }
+                // 平滑的 idf, 只有一篇文档时退化为词频排序
+                let mut scored: Vec<(String, f64)> = tf
+                    .into_iter()
+                    .map(|(token, count)| {
+                        let idf = ((1.0 + n) / (1.0 + df[token] as f64)).ln() + 1.0;
+                        (token.to_string(), count as f64 / tokens.len() as f64 * idf)
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                scored.truncate(top_k);
+                scored
+            })
+            .collect();
+
+        Ok(result)
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Bm25Index>()?;
+    m.add_function(wrap_pyfunction!(extract_keywords, m)?)?;
+    Ok(())
+}
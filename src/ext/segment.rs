@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::RwLock;
+
+use jieba_rs::Jieba;
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// 全局分词器, 加载自定义词典后对所有调用生效
+static JIEBA: Lazy<RwLock<Jieba>> = Lazy::new(|| RwLock::new(Jieba::new()));
+
+pub fn warm_up() {
+    Lazy::force(&JIEBA);
+}
+
+// 供其他模块复用的分词入口
+pub fn cut(text: &str, mode: &str, hmm: bool) -> PyResult<Vec<String>> {
+    let jieba = JIEBA.read().unwrap();
+    let words = match mode {
+        "default" => jieba.cut(text, hmm),
+        "all" => jieba.cut_all(text),
+        "search" => jieba.cut_for_search(text, hmm),
+        _ => return Err(PyValueError::new_err(format!("未知的分词模式: {}", mode))),
+    };
+    Ok(words.into_iter().map(str::to_string).collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, mode="default", hmm=true))]
+pub fn segment(text: &str, mode: &str, hmm: bool) -> PyResult<Vec<String>> {
+    cut(text, mode, hmm)
+}
+
+#[pyfunction]
+pub fn load_user_dict(path: &str) -> PyResult<()> {
+    let file = File::open(path)
+        .map_err(|e| PyValueError::new_err(format!("无法打开词典 {}: {}", path, e)))?;
+    JIEBA
+        .write()
+        .unwrap()
+        .load_dict(&mut BufReader::new(file))
+        .map_err(|e| PyValueError::new_err(format!("词典格式错误 {}: {}", path, e)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (words, freq=None))]
+pub fn add_words(words: Vec<String>, freq: Option<usize>) -> PyResult<()> {
+    let mut jieba = JIEBA.write().unwrap();
+    for word in words.iter().filter(|w| !w.trim().is_empty()) {
+        jieba.add_word(word.trim(), freq, None);
+    }
+    Ok(())
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(segment, m)?)?;
+    m.add_function(wrap_pyfunction!(load_user_dict, m)?)?;
+    m.add_function(wrap_pyfunction!(add_words, m)?)?;
+    Ok(())
+}
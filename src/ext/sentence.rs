@@ -0,0 +1,171 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::matcher::byte_to_char_offsets;
+
+const OPEN_QUOTES: [char; 4] = ['“', '‘', '「', '『'];
+const CLOSE_QUOTES: [char; 4] = ['”', '’', '」', '』'];
+// 句末标点之后仍属于本句的闭合符号
+const TRAILING: [char; 8] = ['”', '’', '」', '』', '）', ')', '"', '\''];
+
+// pos 处句末分隔符的字节长度, 未指定分隔符时的规则同 count_sentences, 另外 '.' 后为闭合符号时也视为句末
+fn delimiter_len(text: &str, pos: usize, delimiters: Option<&[String]>) -> Option<usize> {
+    let rest = &text[pos..];
+    if let Some(delimiters) = delimiters {
+        return delimiters
+            .iter()
+            .find(|d| !d.is_empty() && rest.starts_with(d.as_str()))
+            .map(String::len);
+    }
+    let mut chars = rest.chars();
+    let c = chars.next()?;
+    let end = match c {
+        '。' | '！' | '？' | '!' | '?' | '；' | '…' => true,
+        '.' => chars
+            .next()
+            .is_none_or(|n| n.is_whitespace() || TRAILING.contains(&n)),
+        _ => false,
+    };
+    end.then(|| c.len_utf8())
+}
+
+// 按句末标点切分, 返回各句的字节区间; 连续的句末标点与其后的闭合引号、括号归入同一句,
+// 引号内的句末标点不切分, 引号以句末标点结束时在闭合引号之后切分
+pub fn sentence_byte_spans(text: &str, delimiters: Option<&[String]>) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut ascii_open = false;
+    let mut after_end = false;
+    let mut pos = 0;
+    let push = |spans: &mut Vec<(usize, usize)>, start: usize, end: usize| {
+        let sentence = &text[start..end];
+        let trimmed = sentence.trim_start();
+        let start = start + sentence.len() - trimmed.len();
+        let end = start + trimmed.trim_end().len();
+        if start < end {
+            spans.push((start, end));
+        }
+    };
+
+    while pos < text.len() {
+        let c = text[pos..].chars().next().unwrap();
+        let quoted = depth > 0 || ascii_open;
+        if let Some(len) = delimiter_len(text, pos, delimiters) {
+            pos += len;
+            if quoted {
+                after_end = true;
+                continue;
+            }
+        } else {
+            pos += c.len_utf8();
+            if OPEN_QUOTES.contains(&c) {
+                depth += 1;
+            } else if CLOSE_QUOTES.contains(&c) {
+                depth = depth.saturating_sub(1);
+            } else if c == '"' {
+                ascii_open = !ascii_open;
+            }
+            // 引号以句末标点结束且已全部闭合
+            let closed = quoted && depth == 0 && !ascii_open;
+            if !(closed && after_end) {
+                if !c.is_whitespace() {
+                    after_end = false;
+                }
+                continue;
+            }
+        }
+        // 吸收紧随其后的句末标点与闭合符号
+        while pos < text.len() {
+            if let Some(len) = delimiter_len(text, pos, delimiters) {
+                pos += len;
+                continue;
+            }
+            let c = text[pos..].chars().next().unwrap();
+            if !TRAILING.contains(&c) {
+                break;
+            }
+            if c == '"' {
+                // 此处的 '"' 只能是闭合引号, 未处于引号内时视为下一句的开引号
+                if !ascii_open {
+                    break;
+                }
+                ascii_open = false;
+            } else if CLOSE_QUOTES.contains(&c) {
+                depth = depth.saturating_sub(1);
+            }
+            pos += c.len_utf8();
+        }
+        push(&mut spans, start, pos);
+        start = pos;
+        after_end = false;
+    }
+    push(&mut spans, start, text.len());
+    spans
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, delimiters=None, unit="char"))]
+pub fn sentence_spans(
+    text: &str,
+    delimiters: Option<Vec<String>>,
+    unit: &str,
+) -> PyResult<Vec<(usize, usize)>> {
+    let spans = sentence_byte_spans(text, delimiters.as_deref());
+    match unit {
+        "byte" => Ok(spans),
+        "char" => {
+            let offsets: Vec<usize> = spans.iter().flat_map(|&(s, e)| [s, e]).collect();
+            let chars = byte_to_char_offsets(text, &offsets);
+            Ok(chars.chunks(2).map(|p| (p[0], p[1])).collect())
+        }
+        _ => Err(PyValueError::new_err(format!(
+            "未知的 unit: {}, 可选 char / byte",
+            unit
+        ))),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (sentences, center_indices, before=1, after=1))]
+pub fn context_windows(
+    sentences: Vec<(usize, usize)>,
+    center_indices: Vec<usize>,
+    before: usize,
+    after: usize,
+) -> PyResult<Vec<(usize, usize, Vec<usize>)>> {
+    if let Some(&i) = center_indices.iter().find(|&&i| i >= sentences.len()) {
+        return Err(PyValueError::new_err(format!(
+            "句子下标 {} 超出范围, 共 {} 句",
+            i,
+            sentences.len()
+        )));
+    }
+    let mut centers = center_indices;
+    centers.sort_unstable();
+    centers.dedup();
+
+    // (首句下标, 末句下标, 中心句下标), 重叠或相邻的窗口合并
+    let mut windows: Vec<(usize, usize, Vec<usize>)> = Vec::new();
+    for i in centers {
+        let first = i.saturating_sub(before);
+        let last = i.saturating_add(after).min(sentences.len() - 1);
+        match windows.last_mut() {
+            Some(window) if first <= window.1 + 1 => {
+                window.1 = window.1.max(last);
+                window.2.push(i);
+            }
+            _ => windows.push((first, last, vec![i])),
+        }
+    }
+    Ok(windows
+        .into_iter()
+        .map(|(first, last, centers)| (sentences[first].0, sentences[last].1, centers))
+        .collect())
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(sentence_spans, m)?)?;
+    m.add_function(wrap_pyfunction!(context_windows, m)?)?;
+    Ok(())
+}
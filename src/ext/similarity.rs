@@ -0,0 +1,373 @@
+use std::collections::HashSet;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::clean::to_half_width;
+use super::threads::install;
+
+// 两行滚动数组计算编辑距离, 超过 limit 时提前返回 limit + 1
+pub fn levenshtein<T: PartialEq>(a: &[T], b: &[T], limit: usize) -> usize {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    if a.len() - b.len() > limit {
+        return limit + 1;
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        let mut row_min = curr[0];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+            row_min = row_min.min(curr[j + 1]);
+        }
+        // 整行都已超过上限, 最终结果不可能更小
+        if row_min > limit {
+            return limit + 1;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()].min(limit + 1)
+}
+
+// 按较长字符串长度归一化的相似度
+pub fn normalized_similarity<T: PartialEq>(a: &[T], b: &[T], min_similarity: f64) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let limit = ((1.0 - min_similarity.clamp(0.0, 1.0)) * longest as f64).floor() as usize;
+    let distance = levenshtein(a, b, limit);
+    if distance > limit {
+        return 0.0;
+    }
+    1.0 - distance as f64 / longest as f64
+}
+
+// 相似度达到阈值时两者长度差的上限
+pub fn max_length_diff(len: usize, threshold: f64) -> usize {
+    ((1.0 - threshold.clamp(0.0, 1.0)) * len as f64 / threshold.max(f64::EPSILON)).floor() as usize
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b, max_distance=None))]
+pub fn edit_distance(a: &str, b: &str, max_distance: Option<usize>) -> PyResult<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    Ok(levenshtein(&a, &b, max_distance.unwrap_or(usize::MAX - 1)))
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b, min_similarity=0.0))]
+pub fn similarity(a: &str, b: &str, min_similarity: f64) -> PyResult<f64> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    Ok(normalized_similarity(&a, &b, min_similarity))
+}
+
+// 字符 n-gram 集合, 短于 n 的非空串整体作为一个 gram
+pub fn char_ngrams(chars: &[char], n: usize) -> HashSet<&[char]> {
+    if chars.len() < n {
+        return chars.chunks(n).collect();
+    }
+    chars.windows(n).collect()
+}
+
+pub fn jaccard(a: &HashSet<&[char]>, b: &HashSet<&[char]>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let inter = a.intersection(b).count();
+    inter as f64 / (a.len() + b.len() - inter) as f64
+}
+
+#[pyfunction]
+#[pyo3(signature = (strings, metric="levenshtein", n=2))]
+pub fn similarity_matrix(
+    py: Python<'_>,
+    strings: Vec<String>,
+    metric: &str,
+    n: usize,
+) -> PyResult<Vec<f64>> {
+    py.allow_threads(|| {
+        if n == 0 {
+            return Err(PyValueError::new_err("n 必须大于 0"));
+        }
+        let chars: Vec<Vec<char>> = strings.iter().map(|s| s.chars().collect()).collect();
+        let size = chars.len();
+        let pair: Box<dyn Fn(usize, usize) -> f64 + Sync> = match metric {
+            "levenshtein" => Box::new(|i, j| normalized_similarity(&chars[i], &chars[j], 0.0)),
+            "jaccard" => {
+                let grams: Vec<HashSet<&[char]>> =
+                    chars.iter().map(|c| char_ngrams(c, n)).collect();
+                Box::new(move |i, j| jaccard(&grams[i], &grams[j]))
+            }
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "未知的相似度度量: {}",
+                    metric
+                )))
+            }
+        };
+
+        // 只计算上三角, 再对称填充
+        let upper: Vec<Vec<f64>> = install(|| {
+            (0..size)
+                .into_par_iter()
+                .map(|i| (i + 1..size).map(|j| pair(i, j)).collect())
+                .collect()
+        });
+        let mut matrix = vec![1.0; size * size];
+        for (i, row) in upper.iter().enumerate() {
+            for (k, &value) in row.iter().enumerate() {
+                let j = i + 1 + k;
+                matrix[i * size + j] = value;
+                matrix[j * size + i] = value;
+            }
+        }
+
+        Ok(matrix)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (a, b, n=2))]
+pub fn ngram_jaccard(a: &str, b: &str, n: usize) -> PyResult<f64> {
+    if n == 0 {
+        return Err(PyValueError::new_err("n 必须大于 0"));
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    Ok(jaccard(&char_ngrams(&a, n), &char_ngrams(&b, n)))
+}
+
+// 将逐字符对齐合并为两侧都连续的区间 (a 起, a 止, b 起, b 止)
+fn merge_spans(pairs: &[(usize, usize)]) -> Vec<(usize, usize, usize, usize)> {
+    let mut spans: Vec<(usize, usize, usize, usize)> = Vec::new();
+    for &(i, j) in pairs {
+        match spans.last_mut() {
+            Some(last) if last.1 == i && last.3 == j => {
+                last.1 += 1;
+                last.3 += 1;
+            }
+            _ => spans.push((i, i + 1, j, j + 1)),
+        }
+    }
+    spans
+}
+
+#[pyfunction]
+pub fn lcs(
+    py: Python<'_>,
+    a: &str,
+    b: &str,
+) -> PyResult<(usize, Vec<(usize, usize, usize, usize)>)> {
+    py.allow_threads(|| {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let width = b.len() + 1;
+        // table[i * width + j] 为 a[i..] 与 b[j..] 的最长公共子序列长度
+        let mut table = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                table[i * width + j] = if a[i] == b[j] {
+                    table[(i + 1) * width + j + 1] + 1
+                } else {
+                    table[(i + 1) * width + j].max(table[i * width + j + 1])
+                };
+            }
+        }
+
+        let mut pairs = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i] == b[j] {
+                pairs.push((i, j));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Ok((pairs.len(), merge_spans(&pairs)))
+    })
+}
+
+#[pyfunction]
+pub fn longest_common_substring(
+    py: Python<'_>,
+    a: &str,
+    b: &str,
+) -> PyResult<(usize, Vec<(usize, usize, usize, usize)>)> {
+    py.allow_threads(|| {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        // 以 a[i - 1] 与 b[j - 1] 结尾的公共子串长度, 滚动一行
+        let mut prev = vec![0usize; b.len() + 1];
+        let mut curr = vec![0usize; b.len() + 1];
+        let mut best = (0, 0, 0);
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                curr[j] = if a[i - 1] == b[j - 1] {
+                    prev[j - 1] + 1
+                } else {
+                    0
+                };
+                if curr[j] > best.0 {
+                    best = (curr[j], i, j);
+                }
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        let (len, a_end, b_end) = best;
+        if len == 0 {
+            return Ok((0, Vec::new()));
+        }
+        Ok((len, vec![(a_end - len, a_end, b_end - len, b_end)]))
+    })
+}
+
+// 去掉公共前后缀, 不影响编辑距离但能缩小动态规划规模
+fn strip_common_affix<'a>(a: &'a [char], b: &'a [char]) -> (&'a [char], &'a [char]) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (&a[..a.len() - suffix], &b[..b.len() - suffix])
+}
+
+#[pyfunction]
+#[pyo3(signature = (query, candidates, k=5, min_score=0.0))]
+pub fn fuzzy_match(
+    py: Python<'_>,
+    query: &str,
+    candidates: Vec<String>,
+    k: usize,
+    min_score: f64,
+) -> PyResult<Vec<(usize, f64)>> {
+    py.allow_threads(|| {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let query: Vec<char> = query.chars().collect();
+        // 当前入选的 (得分, 下标), 按得分降序
+        let mut best: Vec<(f64, usize)> = Vec::with_capacity(k + 1);
+
+        for (index, candidate) in candidates.iter().enumerate() {
+            let candidate: Vec<char> = candidate.chars().collect();
+            let longest = query.len().max(candidate.len());
+            let cutoff = if best.len() == k {
+                best[k - 1].0.max(min_score)
+            } else {
+                min_score
+            };
+            let score = if longest == 0 {
+                1.0
+            } else {
+                // 长度差决定了得分上限, 无望入选时跳过
+                let diff = query.len().abs_diff(candidate.len());
+                if 1.0 - (diff as f64 / longest as f64) < cutoff {
+                    continue;
+                }
+                let limit = ((1.0 - cutoff.clamp(0.0, 1.0)) * longest as f64).floor() as usize;
+                let (a, b) = strip_common_affix(&query, &candidate);
+                let distance = levenshtein(a, b, limit);
+                if distance > limit {
+                    continue;
+                }
+                1.0 - distance as f64 / longest as f64
+            };
+            if score < cutoff || (best.len() == k && score <= best[k - 1].0) {
+                continue;
+            }
+            let pos = best.partition_point(|&(s, _)| s >= score);
+            best.insert(pos, (score, index));
+            best.truncate(k);
+        }
+
+        Ok(best
+            .into_iter()
+            .map(|(score, index)| (index, score))
+            .collect())
+    })
+}
+
+// 局部比对的打分: 匹配 +2, 失配与空位 -1
+const ALIGN_MATCH: i32 = 2;
+const ALIGN_PENALTY: i32 = -1;
+
+#[pyfunction]
+pub fn align_evidence(py: Python<'_>, chunk: &str, claim: &str) -> PyResult<(usize, usize, f64)> {
+    py.allow_threads(|| {
+        // 全角与大小写差异不计为失配
+        let normalize = |c: char| to_half_width(c).to_ascii_lowercase();
+        let a: Vec<char> = chunk.chars().map(normalize).collect();
+        let b: Vec<char> = claim.chars().map(normalize).collect();
+        if a.is_empty() || b.is_empty() {
+            return Ok((0, 0, 0.0));
+        }
+        // Smith-Waterman, 滚动一行, 每格记录 (得分, 比对在 chunk 中的起点)
+        let mut prev = vec![(0i32, 0usize); b.len() + 1];
+        let mut curr = vec![(0i32, 0usize); b.len() + 1];
+        // (得分, chunk 起点, chunk 终点), 同分时取最先结束的
+        let mut best = (0, 0, 0);
+        for i in 1..=a.len() {
+            curr[0] = (0, i);
+            for j in 1..=b.len() {
+                let substitution = if a[i - 1] == b[j - 1] {
+                    ALIGN_MATCH
+                } else {
+                    ALIGN_PENALTY
+                };
+                let diagonal = (prev[j - 1].0 + substitution, prev[j - 1].1);
+                let up = (prev[j].0 + ALIGN_PENALTY, prev[j].1);
+                let left = (curr[j - 1].0 + ALIGN_PENALTY, curr[j - 1].1);
+                let mut cell = (0, i);
+                for candidate in [diagonal, up, left] {
+                    if candidate.0 > cell.0 {
+                        cell = candidate;
+                    }
+                }
+                curr[j] = cell;
+                if cell.0 > best.0 {
+                    best = (cell.0, cell.1, i);
+                }
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        let (score, start, end) = best;
+        // 以 claim 完全匹配时的得分归一化
+        Ok((
+            start,
+            end,
+            score as f64 / (ALIGN_MATCH as f64 * b.len() as f64),
+        ))
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(align_evidence, m)?)?;
+    m.add_function(wrap_pyfunction!(edit_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(similarity, m)?)?;
+    m.add_function(wrap_pyfunction!(similarity_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(ngram_jaccard, m)?)?;
+    m.add_function(wrap_pyfunction!(lcs, m)?)?;
+    m.add_function(wrap_pyfunction!(longest_common_substring, m)?)?;
+    m.add_function(wrap_pyfunction!(fuzzy_match, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+
+use super::clean::is_cjk;
+
+pub fn is_punct(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(c,
+            '\u{2010}'..='\u{2027}'
+            | '\u{3001}'..='\u{3011}'
+            | '\u{3014}'..='\u{301F}'
+            | '\u{FF01}'..='\u{FF0F}'
+            | '\u{FF1A}'..='\u{FF20}'
+            | '\u{FF3B}'..='\u{FF40}'
+            | '\u{FF5B}'..='\u{FF65}')
+}
+
+pub fn is_ideograph(c: char) -> bool {
+    is_cjk(c) && !is_punct(c) && !c.is_whitespace()
+}
+
+// 以句末标点切分, 返回非空句子数
+pub fn count_sentences(text: &str) -> usize {
+    let mut count = 0;
+    let mut has_content = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let end = match c {
+            '。' | '！' | '？' | '!' | '?' | '；' | '…' => true,
+            '.' => chars.peek().is_none_or(|n| n.is_whitespace()),
+            _ => false,
+        };
+        if end {
+            if has_content {
+                count += 1;
+            }
+            has_content = false;
+        } else if !c.is_whitespace() && !is_punct(c) {
+            has_content = true;
+        }
+    }
+    count + has_content as usize
+}
+
+#[pyclass(frozen, get_all)]
+pub struct TextStats {
+    pub char_count: usize,
+    pub cjk_ratio: f64,
+    pub digit_ratio: f64,
+    pub punct_ratio: f64,
+    pub sentence_count: usize,
+    pub avg_sentence_len: f64,
+}
+
+#[pymethods]
+impl TextStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "TextStats(char_count={}, cjk_ratio={:.3}, digit_ratio={:.3}, punct_ratio={:.3}, sentence_count={}, avg_sentence_len={:.1})",
+            self.char_count,
+            self.cjk_ratio,
+            self.digit_ratio,
+            self.punct_ratio,
+            self.sentence_count,
+            self.avg_sentence_len
+        )
+    }
+}
+
+#[pyfunction]
+pub fn text_stats(text: &str) -> PyResult<TextStats> {
+    let mut char_count = 0;
+    let mut visible = 0;
+    let mut cjk = 0;
+    let mut digit = 0;
+    let mut punct = 0;
+    for c in text.chars() {
+        char_count += 1;
+        if c.is_whitespace() {
+            continue;
+        }
+        visible += 1;
+        if is_punct(c) {
+            punct += 1;
+        } else if is_ideograph(c) {
+            cjk += 1;
+        } else if c.is_numeric() {
+            digit += 1;
+        }
+    }
+    let sentence_count = count_sentences(text);
+    let ratio = |n: usize| {
+        if visible == 0 {
+            0.0
+        } else {
+            n as f64 / visible as f64
+        }
+    };
+
+    Ok(TextStats {
+        char_count,
+        cjk_ratio: ratio(cjk),
+        digit_ratio: ratio(digit),
+        punct_ratio: ratio(punct),
+        sentence_count,
+        avg_sentence_len: if sentence_count == 0 {
+            0.0
+        } else {
+            visible as f64 / sentence_count as f64
+        },
+    })
+}
+
+// 高频虚词, 用于区分正文语言与夹杂的术语
+const ZH_COMMON: [char; 20] = [
+    '的', '了', '是', '在', '和', '有', '不', '这', '中', '为', '以', '个', '们', '与', '也', '就',
+    '而', '及', '其', '或',
+];
+const EN_COMMON: [&str; 20] = [
+    "the", "of", "and", "to", "in", "is", "that", "for", "it", "as", "with", "are", "on", "be",
+    "by", "this", "an", "or", "from", "can",
+];
+
+#[pyfunction]
+pub fn detect_language(text: &str) -> PyResult<&'static str> {
+    // 汉字按字计数, 英文按词计数, 两者的信息量大致相当
+    let mut zh = 0;
+    let mut zh_common = 0;
+    for c in text.chars().filter(|&c| is_ideograph(c)) {
+        zh += 1;
+        zh_common += ZH_COMMON.contains(&c) as usize;
+    }
+    let mut en = 0;
+    let mut en_common = 0;
+    for word in text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+    {
+        en += 1;
+        en_common += EN_COMMON.contains(&word.to_ascii_lowercase().as_str()) as usize;
+    }
+    if zh + en == 0 {
+        return Ok("unknown");
+    }
+    let share = zh as f64 / (zh + en) as f64;
+    Ok(if share >= 0.7 {
+        "zh"
+    } else if share <= 0.3 {
+        // 较长却没有任何英文虚词的拉丁字母文本多为代码、公式或其他语言
+        if en >= 20 && en_common == 0 {
+            "unknown"
+        } else {
+            "en"
+        }
+    } else if en_common == 0 {
+        // 中文正文中夹杂的英文术语
+        "zh"
+    } else if zh_common == 0 {
+        "en"
+    } else {
+        "mixed"
+    })
+}
+
+static COMMON_CHARS: Lazy<HashSet<char>> = Lazy::new(|| {
+    include_str!("data/common_chars.txt")
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .flat_map(str::chars)
+        .collect()
+});
+
+pub fn warm_up() {
+    Lazy::force(&COMMON_CHARS);
+}
+
+// 公式与符号: 运算符、希腊字母、上下标、箭头与数学运算符区
+fn is_formula_symbol(c: char) -> bool {
+    matches!(c,
+        '=' | '+' | '*' | '/' | '^' | '_' | '\\' | '{' | '}' | '<' | '>' | '|' | '±' | '×' | '÷'
+        | '\u{0370}'..='\u{03FF}'
+        | '\u{2070}'..='\u{209F}'
+        | '\u{2190}'..='\u{21FF}'
+        | '\u{2200}'..='\u{22FF}')
+}
+
+#[pyfunction]
+pub fn difficulty_score(text: &str) -> PyResult<f64> {
+    let mut visible = 0;
+    let mut ideographs = 0;
+    let mut rare = 0;
+    let mut symbols = 0;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        visible += 1;
+        if is_ideograph(c) {
+            ideographs += 1;
+            rare += !COMMON_CHARS.contains(&c) as usize;
+        } else if is_formula_symbol(c) {
+            symbols += 1;
+        }
+    }
+    if visible == 0 {
+        return Ok(0.0);
+    }
+    // 各项线性映射到 [0, 1]: 平均句长 10 到 60 字, 生僻字占汉字 0 到 10%, 符号占可见字符 0 到 15%
+    let sentence_len = visible as f64 / count_sentences(text).max(1) as f64;
+    let sentence_len = ((sentence_len - 10.0) / 50.0).clamp(0.0, 1.0);
+    let rare = if ideographs == 0 {
+        0.0
+    } else {
+        (rare as f64 / ideographs as f64 / 0.1).min(1.0)
+    };
+    let symbols = (symbols as f64 / visible as f64 / 0.15).min(1.0);
+    Ok(0.4 * sentence_len + 0.3 * rare + 0.3 * symbols)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<TextStats>()?;
+    m.add_function(wrap_pyfunction!(text_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_language, m)?)?;
+    m.add_function(wrap_pyfunction!(difficulty_score, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+
+use super::stats::is_punct;
+
+// 内置中英文停用词, 每行一个
+static STOPWORDS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    include_str!("data/stopwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect()
+});
+
+pub fn warm_up() {
+    Lazy::force(&STOPWORDS);
+}
+
+// 内置停用词, 以及仅由空白与标点构成的词
+pub fn is_stopword(token: &str) -> bool {
+    let token = token.trim();
+    token.chars().all(|c| c.is_whitespace() || is_punct(c))
+        || STOPWORDS.contains(token)
+        || STOPWORDS.contains(token.to_lowercase().as_str())
+}
+
+#[pyfunction]
+#[pyo3(signature = (tokens, extra_stopwords=None))]
+pub fn filter_stopwords(
+    tokens: Vec<String>,
+    extra_stopwords: Option<Vec<String>>,
+) -> PyResult<Vec<String>> {
+    let extra: HashSet<String> = extra_stopwords
+        .unwrap_or_default()
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .collect();
+    Ok(tokens
+        .into_iter()
+        .filter(|t| !is_stopword(t) && !extra.contains(&t.trim().to_lowercase()))
+        .collect())
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(filter_stopwords, m)?)?;
+    Ok(())
+}
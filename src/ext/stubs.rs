@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use pyo3::exceptions::{PyBaseException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyModule, PyType};
+
+use super::SUBMODULES;
+
+// 参数名与默认值, 可变参数记为 *args / **kwargs
+type Params<'py> = Vec<(String, Option<Bound<'py, PyAny>>)>;
+
+// 存根中 def 的参数, 默认值为 AST 节点
+fn stub_params<'py>(def: &Bound<'py, PyAny>) -> PyResult<Params<'py>> {
+    let args = def.getattr("args")?;
+    let mut positional: Vec<Bound<'py, PyAny>> = args.getattr("posonlyargs")?.extract()?;
+    positional.extend(args.getattr("args")?.extract::<Vec<_>>()?);
+    let defaults: Vec<Bound<'py, PyAny>> = args.getattr("defaults")?.extract()?;
+    // 默认值与末尾的若干个参数对应
+    let first_default = positional.len() - defaults.len();
+    let mut params = Vec::new();
+    for (i, arg) in positional.iter().enumerate() {
+        let default = i.checked_sub(first_default).map(|j| defaults[j].clone());
+        params.push((arg.getattr("arg")?.extract()?, default));
+    }
+    if let Ok(vararg) = args.getattr("vararg")?.getattr("arg") {
+        params.push((format!("*{}", vararg.extract::<String>()?), None));
+    }
+    let kwonly: Vec<Bound<'py, PyAny>> = args.getattr("kwonlyargs")?.extract()?;
+    let kw_defaults: Vec<Bound<'py, PyAny>> = args.getattr("kw_defaults")?.extract()?;
+    for (arg, default) in kwonly.iter().zip(kw_defaults) {
+        params.push((
+            arg.getattr("arg")?.extract()?,
+            (!default.is_none()).then_some(default),
+        ));
+    }
+    if let Ok(kwarg) = args.getattr("kwarg")?.getattr("arg") {
+        params.push((format!("**{}", kwarg.extract::<String>()?), None));
+    }
+    Ok(params)
+}
+
+// 运行时的参数, 来自 __text_signature__; 没有签名时返回 None, 不做比较
+fn runtime_params<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Option<Params<'py>>> {
+    let py = obj.py();
+    let inspect = py.import_bound("inspect")?;
+    let Ok(signature) = inspect.call_method1("signature", (obj,)) else {
+        return Ok(None);
+    };
+    let empty = inspect.getattr("Parameter")?.getattr("empty")?;
+    let mut params = Vec::new();
+    for param in signature
+        .getattr("parameters")?
+        .call_method0("values")?
+        .iter()?
+    {
+        let param = param?;
+        let name: String = param.getattr("name")?.extract()?;
+        let name = match param
+            .getattr("kind")?
+            .getattr("name")?
+            .extract::<String>()?
+            .as_str()
+        {
+            "VAR_POSITIONAL" => format!("*{name}"),
+            "VAR_KEYWORD" => format!("**{name}"),
+            _ => name,
+        };
+        let default = param.getattr("default")?;
+        params.push((name, (!default.is(&empty)).then_some(default)));
+    }
+    Ok(Some(params))
+}
+
+fn param_list(params: &Params<'_>) -> String {
+    let names: Vec<&str> = params.iter().map(|(name, _)| name.as_str()).collect();
+    format!("({})", names.join(", "))
+}
+
+fn compare_params(
+    problems: &mut Vec<String>,
+    name: &str,
+    stub: &Params<'_>,
+    runtime: &Params<'_>,
+) -> PyResult<()> {
+    if stub.iter().map(|p| &p.0).ne(runtime.iter().map(|p| &p.0)) {
+        problems.push(format!(
+            "{name} 的参数不一致: 存根为 {}, 运行时为 {}",
+            param_list(stub),
+            param_list(runtime)
+        ));
+        return Ok(());
+    }
+    for ((param, stub_default), (_, default)) in stub.iter().zip(runtime) {
+        match (stub_default, default) {
+            (None, None) => {}
+            (Some(_), None) => problems.push(format!("{name} 的参数 {param} 在运行时没有默认值")),
+            (None, Some(_)) => problems.push(format!("{name} 的参数 {param} 在存根中缺少默认值")),
+            (Some(node), Some(default)) => {
+                let py = node.py();
+                // 运行时无法表示为字面量的默认值显示为 ..., 存根中非字面量的默认值同样跳过
+                if default.is(&py.Ellipsis()) {
+                    continue;
+                }
+                let ast = py.import_bound("ast")?;
+                let Ok(value) = ast.call_method1("literal_eval", (node,)) else {
+                    continue;
+                };
+                if !value.eq(default)? {
+                    problems.push(format!(
+                        "{name} 的参数 {param} 默认值不一致: 存根为 {}, 运行时为 {}",
+                        ast.call_method1("unparse", (node,))?,
+                        default.repr()?
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// 存根中顶层或类体内的 def / class / 属性标注, 按名称索引
+fn stub_defs<'py>(body: &Bound<'py, PyAny>) -> PyResult<HashMap<String, Bound<'py, PyAny>>> {
+    let mut defs = HashMap::new();
+    for node in body.iter()? {
+        let node = node?;
+        let kind = node.get_type().name()?.to_string();
+        let name = match kind.as_str() {
+            "FunctionDef" | "AsyncFunctionDef" | "ClassDef" => node.getattr("name")?,
+            "AnnAssign" => match node.getattr("target")?.getattr("id") {
+                Ok(id) => id,
+                Err(_) => continue,
+            },
+            _ => continue,
+        };
+        defs.insert(name.extract()?, node);
+    }
+    Ok(defs)
+}
+
+fn is_def(node: &Bound<'_, PyAny>) -> PyResult<bool> {
+    Ok(matches!(
+        node.get_type().name()?.to_string().as_str(),
+        "FunctionDef" | "AsyncFunctionDef"
+    ))
+}
+
+fn check_function(
+    problems: &mut Vec<String>,
+    name: &str,
+    stub: &Bound<'_, PyAny>,
+    obj: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    if !is_def(stub)? {
+        problems.push(format!("{name} 在存根中不是函数"));
+        return Ok(());
+    }
+    if let Some(runtime) = runtime_params(obj)? {
+        compare_params(problems, name, &stub_params(stub)?, &runtime)?;
+    }
+    Ok(())
+}
+
+fn check_class(
+    problems: &mut Vec<String>,
+    name: &str,
+    stub: &Bound<'_, PyAny>,
+    cls: &Bound<'_, PyType>,
+) -> PyResult<()> {
+    if stub.get_type().name()? != "ClassDef" {
+        problems.push(format!("{name} 在存根中不是类"));
+        return Ok(());
+    }
+    let py = cls.py();
+    if cls.is_subclass(&py.get_type_bound::<PyBaseException>())? {
+        return Ok(());
+    }
+    let members = stub_defs(&stub.getattr("body")?)?;
+
+    // 构造函数的运行时签名不含 self
+    if cls
+        .getattr("__text_signature__")?
+        .extract::<String>()
+        .is_ok()
+    {
+        match members.get("__init__") {
+            Some(init) => {
+                if let Some(runtime) = runtime_params(cls.as_any())? {
+                    let mut params = stub_params(init)?;
+                    if params.first().is_some_and(|(p, _)| p == "self") {
+                        params.remove(0);
+                    }
+                    compare_params(problems, &format!("{name}.__init__"), &params, &runtime)?;
+                }
+            }
+            None => problems.push(format!("存根缺少 {name}.__init__")),
+        }
+    }
+
+    let attrs = cls.getattr("__dict__")?.call_method0("copy")?;
+    let mut seen = vec!["__init__".to_string()];
+    for key in attrs.iter()? {
+        let key: String = key?.extract()?;
+        // __repr__ 等由 object 提供的方法不要求出现在存根中
+        let public = !key.starts_with('_') || matches!(key.as_str(), "__len__" | "__contains__");
+        if !public {
+            continue;
+        }
+        let qualname = format!("{name}.{key}");
+        let Some(member) = members.get(&key) else {
+            problems.push(format!("存根缺少 {qualname}"));
+            continue;
+        };
+        seen.push(key.clone());
+        let value = attrs.get_item(&key)?;
+        let kind = value.get_type().name()?.to_string();
+        // 属性在存根中可以是标注或 property
+        if kind == "getset_descriptor" || !is_def(member)? {
+            continue;
+        }
+        let method = if kind == "staticmethod" {
+            cls.getattr(key.as_str())?
+        } else {
+            value
+        };
+        if let Some(runtime) = runtime_params(&method)? {
+            compare_params(problems, &qualname, &stub_params(member)?, &runtime)?;
+        }
+    }
+    let mut extra: Vec<&String> = members
+        .keys()
+        .filter(|k| !seen.contains(k) && !k.starts_with('_'))
+        .collect();
+    extra.sort();
+    for key in extra {
+        if !cls.hasattr(key.as_str())? {
+            problems.push(format!("存根中的 {name}.{key} 在运行时不存在"));
+        }
+    }
+    Ok(())
+}
+
+fn check_module(
+    m: &Bound<'_, PyModule>,
+    file: &str,
+    source: &str,
+    problems: &mut Vec<String>,
+) -> PyResult<()> {
+    let py = m.py();
+    let tree = py
+        .import_bound("ast")?
+        .call_method1("parse", (source, file))?;
+    let defs = stub_defs(&tree.getattr("body")?)?;
+    let mut found = Vec::new();
+    let mut module_problems = Vec::new();
+
+    // 按注册顺序检查
+    for (key, value) in m.dict().iter() {
+        let name: String = key.extract()?;
+        let is_class = value.downcast::<PyType>().is_ok();
+        if name.starts_with('_') || !(is_class || value.is_instance_of::<PyCFunction>()) {
+            continue;
+        }
+        let Some(stub) = defs.get(&name) else {
+            module_problems.push(format!("存根缺少 {name}"));
+            continue;
+        };
+        found.push(name.clone());
+        match value.downcast::<PyType>() {
+            Ok(cls) => check_class(&mut module_problems, &name, stub, cls)?,
+            Err(_) => check_function(&mut module_problems, &name, stub, &value)?,
+        }
+    }
+    let mut extra: Vec<&String> = defs
+        .keys()
+        .filter(|k| !found.contains(k) && !k.starts_with('_'))
+        .collect();
+    extra.sort();
+    for name in extra {
+        module_problems.push(format!("存根中的 {name} 在运行时不存在"));
+    }
+    problems.extend(module_problems.into_iter().map(|p| format!("{file}: {p}")));
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(pass_module)]
+pub fn check_stubs(m: &Bound<'_, PyModule>, stub_dir: &str) -> PyResult<Vec<String>> {
+    let dir = Path::new(stub_dir);
+    let mut modules = vec![("__init__.pyi".to_string(), m.clone())];
+    for name in SUBMODULES {
+        modules.push((format!("{name}.pyi"), m.getattr(name)?.downcast_into()?));
+    }
+
+    let mut problems = Vec::new();
+    for (file, module) in modules {
+        let path = dir.join(&file);
+        let source = fs::read_to_string(&path)
+            .map_err(|e| PyValueError::new_err(format!("无法读取 {}: {}", path.display(), e)))?;
+        check_module(&module, &file, &source, &mut problems)?;
+    }
+    Ok(problems)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(check_stubs, m)?)?;
+    Ok(())
+}
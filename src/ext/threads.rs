@@ -0,0 +1,48 @@
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+// 通过 set_num_threads 配置的线程池, 未配置时使用 rayon 全局线程池 (默认占用全部核心)
+static POOL: Lazy<RwLock<Option<Arc<ThreadPool>>>> = Lazy::new(|| RwLock::new(None));
+
+// 在配置的线程池中执行并行计算
+pub fn install<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    let pool = POOL.read().unwrap().clone();
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+#[pyfunction]
+pub fn set_num_threads(n: usize) -> PyResult<()> {
+    let pool = if n == 0 {
+        None
+    } else {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(n)
+            .thread_name(|i| format!("coursekg-{}", i))
+            .build()
+            .map_err(|e| PyValueError::new_err(format!("无法创建线程池: {}", e)))?;
+        Some(Arc::new(pool))
+    };
+    *POOL.write().unwrap() = pool;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn get_num_threads() -> PyResult<usize> {
+    Ok(match POOL.read().unwrap().as_ref() {
+        Some(pool) => pool.current_num_threads(),
+        None => rayon::current_num_threads(),
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(set_num_threads, m)?)?;
+    m.add_function(wrap_pyfunction!(get_num_threads, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::stats::{is_ideograph, is_punct};
+use super::threads::install;
+
+// 各分词器的经验比例, 只用于估算分块预算, 与真实分词结果可能相差约一成
+struct Profile {
+    name: &'static str,
+    // 模型名前缀, 不区分大小写
+    prefixes: &'static [&'static str],
+    // 每个汉字的 token 数
+    cjk: f64,
+    // 英文单词平均每个 token 覆盖的字母数
+    chars_per_token: f64,
+    // 每个数字字符的 token 数, 按三位一组切分的分词器为 1/3
+    digit: f64,
+}
+
+const PROFILES: [Profile; 6] = [
+    Profile {
+        name: "cl100k",
+        prefixes: &[
+            "cl100k",
+            "gpt-4",
+            "gpt-3.5",
+            "text-embedding-3",
+            "llama3",
+            "llama-3",
+        ],
+        cjk: 1.1,
+        chars_per_token: 4.0,
+        digit: 1.0 / 3.0,
+    },
+    Profile {
+        name: "o200k",
+        prefixes: &["o200k", "gpt-4o", "gpt-4.1", "o1", "o3", "o4"],
+        cjk: 0.75,
+        chars_per_token: 4.2,
+        digit: 1.0 / 3.0,
+    },
+    Profile {
+        name: "qwen",
+        prefixes: &["qwen"],
+        cjk: 0.7,
+        chars_per_token: 4.0,
+        digit: 1.0,
+    },
+    Profile {
+        name: "glm",
+        prefixes: &["glm", "chatglm"],
+        cjk: 0.65,
+        chars_per_token: 4.0,
+        digit: 1.0,
+    },
+    Profile {
+        name: "deepseek",
+        prefixes: &["deepseek"],
+        cjk: 0.65,
+        chars_per_token: 4.0,
+        digit: 1.0,
+    },
+    Profile {
+        // Llama 2 的词表较小, 多数汉字按字节回退; Llama 3 的词表接近 cl100k
+        name: "llama",
+        prefixes: &["llama"],
+        cjk: 1.4,
+        chars_per_token: 3.8,
+        digit: 1.0,
+    },
+];
+
+fn find_profile(model: &str) -> PyResult<&'static Profile> {
+    let model = model.to_ascii_lowercase();
+    // 取最长的匹配前缀, 使 "gpt-4o" 不落入 "gpt-4"
+    PROFILES
+        .iter()
+        .flat_map(|p| p.prefixes.iter().map(move |prefix| (p, prefix)))
+        .filter(|(_, prefix)| model.starts_with(*prefix))
+        .max_by_key(|(_, prefix)| prefix.len())
+        .map(|(p, _)| p)
+        .ok_or_else(|| {
+            let names: Vec<&str> = PROFILES.iter().map(|p| p.name).collect();
+            PyValueError::new_err(format!(
+                "未知的模型: {}, 可选 {} 及以其开头的模型名",
+                model,
+                names.join(" / ")
+            ))
+        })
+}
+
+fn estimate(text: &str, profile: &Profile) -> usize {
+    let mut tokens = 0.0;
+    // 当前连续英文字母与数字的长度, 两者互斥
+    let mut letters = 0;
+    let mut digits = 0;
+    let mut newline_run = false;
+    for c in text.chars() {
+        if letters > 0 && !c.is_ascii_alphabetic() {
+            tokens += (letters as f64 / profile.chars_per_token).ceil();
+            letters = 0;
+        }
+        if digits > 0 && !c.is_ascii_digit() {
+            tokens += (digits as f64 * profile.digit).ceil();
+            digits = 0;
+        }
+        if c == '\n' {
+            // 连续换行通常合并为一个 token
+            if !newline_run {
+                tokens += 1.0;
+            }
+            newline_run = true;
+            continue;
+        }
+        newline_run = false;
+        if c.is_ascii_alphabetic() {
+            letters += 1;
+        } else if c.is_ascii_digit() {
+            digits += 1;
+        } else if c.is_whitespace() {
+            // 单词前的空格并入单词
+        } else if is_ideograph(c) {
+            tokens += profile.cjk;
+        } else if is_punct(c) {
+            tokens += 1.0;
+        } else {
+            // 其他字符按 UTF-8 字节回退估计
+            tokens += (c.len_utf8() as f64 / 2.0).max(1.0);
+        }
+    }
+    tokens += (letters as f64 / profile.chars_per_token).ceil();
+    tokens += (digits as f64 * profile.digit).ceil();
+    tokens.round() as usize
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, model="qwen"))]
+pub fn count_tokens(text: &str, model: &str) -> PyResult<usize> {
+    Ok(estimate(text, find_profile(model)?))
+}
+
+#[pyfunction]
+#[pyo3(signature = (texts, model="qwen"))]
+pub fn count_tokens_batch(py: Python<'_>, texts: Vec<String>, model: &str) -> PyResult<Vec<usize>> {
+    let profile = find_profile(model)?;
+    Ok(py.allow_threads(|| install(|| texts.par_iter().map(|t| estimate(t, profile)).collect())))
+}
+
+#[pyfunction]
+#[pyo3(signature = (chunks, scores, budget_tokens, template_overhead=0, separator="\n\n", model="qwen"))]
+pub fn pack_context(
+    py: Python<'_>,
+    chunks: Vec<String>,
+    scores: Vec<f64>,
+    budget_tokens: usize,
+    template_overhead: usize,
+    separator: &str,
+    model: &str,
+) -> PyResult<(String, Vec<usize>)> {
+    if chunks.len() != scores.len() {
+        return Err(PyValueError::new_err(format!(
+            "chunks 与 scores 长度不一致: {} != {}",
+            chunks.len(),
+            scores.len()
+        )));
+    }
+    let profile = find_profile(model)?;
+    py.allow_threads(|| {
+        let costs: Vec<usize> =
+            install(|| chunks.par_iter().map(|c| estimate(c, profile)).collect());
+        let separator_cost = estimate(separator, profile);
+        let mut order: Vec<usize> = (0..chunks.len()).collect();
+        order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+
+        // 按得分从高到低贪心选取, 放不下的块跳过, 继续尝试更短的块
+        let mut remaining = budget_tokens.saturating_sub(template_overhead);
+        let mut chosen = Vec::new();
+        for i in order {
+            let cost = costs[i] + if chosen.is_empty() { 0 } else { separator_cost };
+            if cost <= remaining {
+                remaining -= cost;
+                chosen.push(i);
+            }
+        }
+        let packed = chosen
+            .iter()
+            .map(|&i| chunks[i].as_str())
+            .collect::<Vec<_>>()
+            .join(separator);
+        Ok((packed, chosen))
+    })
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(pack_context, m)?)?;
+    m.add_function(wrap_pyfunction!(count_tokens, m)?)?;
+    m.add_function(wrap_pyfunction!(count_tokens_batch, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,607 @@
+use std::collections::{HashMap, HashSet};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::clean::{is_cjk, to_half_width};
+use super::config::CoreConfig;
+use super::dedup::find_root;
+use super::similarity::{char_ngrams, jaccard, max_length_diff, normalized_similarity};
+use super::threads::install;
+
+// 比较用的规范形式: 全角转半角, 小写, 压缩空白, 删除与中文相邻的空白
+pub fn normalize_key(s: &str) -> String {
+    let mut key = String::with_capacity(s.len());
+    for word in s.split_whitespace() {
+        if !(key.is_empty() || key.ends_with(is_cjk) || word.starts_with(is_cjk)) {
+            key.push(' ');
+        }
+        key.extend(word.chars().map(to_half_width).flat_map(char::to_lowercase));
+    }
+    key
+}
+
+// 重复三元组的置信度聚合方式
+fn check_aggregate(aggregate: &str) -> PyResult<()> {
+    if matches!(aggregate, "max" | "noisy_or" | "sum") {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!(
+            "未知的聚合方式: {aggregate}, 可选 max / noisy_or / sum"
+        )))
+    }
+}
+
+fn aggregate_confidence(acc: f64, x: f64, aggregate: &str) -> f64 {
+    match aggregate {
+        "noisy_or" => 1.0 - (1.0 - acc) * (1.0 - x),
+        "sum" => acc + x,
+        _ => acc.max(x),
+    }
+}
+
+// 未给出置信度时每条三元组视为 1.0
+fn confidences_for(confidences: Option<Vec<f64>>, len: usize) -> PyResult<Vec<f64>> {
+    match confidences {
+        Some(c) if c.len() != len => Err(PyValueError::new_err(format!(
+            "置信度数量 {} 与三元组数量 {len} 不一致",
+            c.len()
+        ))),
+        Some(c) => Ok(c),
+        None => Ok(vec![1.0; len]),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, confidences=None, aggregate="max"))]
+pub fn dedup_triples(
+    triples: Vec<(String, String, String)>,
+    confidences: Option<Vec<f64>>,
+    aggregate: &str,
+) -> PyResult<Vec<(String, String, String, usize, f64)>> {
+    check_aggregate(aggregate)?;
+    let confidences = confidences_for(confidences, triples.len())?;
+    let mut index: HashMap<(String, String, String), usize> = HashMap::new();
+    let mut result: Vec<(String, String, String, usize, f64)> = Vec::new();
+
+    for ((head, relation, tail), confidence) in triples.into_iter().zip(confidences) {
+        let key = (
+            normalize_key(&head),
+            normalize_key(&relation),
+            normalize_key(&tail),
+        );
+        match index.get(&key) {
+            Some(&i) => {
+                result[i].3 += 1;
+                result[i].4 = aggregate_confidence(result[i].4, confidence, aggregate);
+            }
+            None => {
+                index.insert(key, result.len());
+                // 保留首次出现时的写法
+                result.push((
+                    head.trim().to_string(),
+                    relation.trim().to_string(),
+                    tail.trim().to_string(),
+                    1,
+                    confidence,
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, entity_threshold=None, relation_map=None, confidences=None, aggregate="max", config=None))]
+pub fn merge_triples(
+    py: Python<'_>,
+    triples: Vec<(String, String, String)>,
+    entity_threshold: Option<f64>,
+    relation_map: Option<HashMap<String, String>>,
+    confidences: Option<Vec<f64>>,
+    aggregate: &str,
+    config: Option<CoreConfig>,
+) -> PyResult<MergedTriples> {
+    let entity_threshold =
+        entity_threshold.unwrap_or_else(|| config.unwrap_or_default().similarity_threshold);
+    check_aggregate(aggregate)?;
+    let confidences = confidences_for(confidences, triples.len())?;
+    let relation_map = relation_map.unwrap_or_default();
+    Ok(py.allow_threads(|| {
+        merge(
+            &triples,
+            entity_threshold,
+            relation_map,
+            confidences,
+            aggregate,
+        )
+    }))
+}
+
+// (合并后的三元组, 写法到规范名的映射)
+type MergedTriples = (
+    Vec<(String, String, String, usize, f64)>,
+    HashMap<String, String>,
+);
+
+fn merge(
+    triples: &[(String, String, String)],
+    entity_threshold: f64,
+    relation_map: HashMap<String, String>,
+    confidences: Vec<f64>,
+    aggregate: &str,
+) -> MergedTriples {
+    // 实体按规范形式归并, 记录首次写法与出现次数
+    let mut entity_index: HashMap<String, usize> = HashMap::new();
+    let mut entities: Vec<(String, usize)> = Vec::new();
+    let mut surfaces: Vec<Vec<String>> = Vec::new();
+    let mut entity_of = |name: &str| {
+        let name = name.trim();
+        let i = *entity_index.entry(normalize_key(name)).or_insert_with(|| {
+            entities.push((name.to_string(), 0));
+            surfaces.push(Vec::new());
+            entities.len() - 1
+        });
+        entities[i].1 += 1;
+        if !surfaces[i].iter().any(|s| s == name) {
+            surfaces[i].push(name.to_string());
+        }
+        i
+    };
+    let ids: Vec<(usize, String, usize)> = triples
+        .iter()
+        .map(|(h, r, t)| (entity_of(h), r.trim().to_string(), entity_of(t)))
+        .collect();
+
+    // 编辑距离相似度或二元组 Jaccard 任一达到阈值即视为同一实体;
+    // 按首字分块, 块内按长度排序, 只比较长度差可能达到阈值的对
+    let keys: Vec<Vec<char>> =
+        entity_index
+            .iter()
+            .fold(vec![Vec::new(); entities.len()], |mut keys, (k, &i)| {
+                keys[i] = k.chars().collect();
+                keys
+            });
+    let grams: Vec<HashSet<&[char]>> = keys.iter().map(|k| char_ngrams(k, 2)).collect();
+    let mut blocks: HashMap<Option<char>, Vec<usize>> = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        blocks.entry(key.first().copied()).or_default().push(i);
+    }
+    let pairs: Vec<(usize, usize)> = install(|| {
+        blocks
+            .into_par_iter()
+            .flat_map_iter(|(_, mut block)| {
+                block.sort_by_key(|&i| (keys[i].len(), i));
+                let mut pairs = Vec::new();
+                for (x, &i) in block.iter().enumerate() {
+                    let limit = keys[i].len() + max_length_diff(keys[i].len(), entity_threshold);
+                    for &j in block[x + 1..]
+                        .iter()
+                        .take_while(|&&j| keys[j].len() <= limit)
+                    {
+                        let similar = normalized_similarity(&keys[i], &keys[j], entity_threshold)
+                            >= entity_threshold
+                            || jaccard(&grams[i], &grams[j]) >= entity_threshold;
+                        if similar {
+                            pairs.push((i.min(j), i.max(j)));
+                        }
+                    }
+                }
+                pairs
+            })
+            .collect()
+    });
+    let mut parent: Vec<usize> = (0..keys.len()).collect();
+    for (i, j) in pairs {
+        let (ri, rj) = (find_root(&mut parent, i), find_root(&mut parent, j));
+        parent[ri.max(rj)] = ri.min(rj);
+    }
+
+    // 每组取出现次数最多的写法为规范名, 同频时取先出现者
+    let mut canonical: Vec<usize> = (0..keys.len()).collect();
+    for i in 0..keys.len() {
+        let root = find_root(&mut parent, i);
+        let best = canonical[root];
+        if entities[i].1 > entities[best].1 {
+            canonical[root] = i;
+        }
+    }
+    let names: Vec<String> = (0..keys.len())
+        .map(|i| entities[canonical[find_root(&mut parent, i)]].0.clone())
+        .collect();
+
+    let relation_map: HashMap<String, String> = relation_map
+        .into_iter()
+        .map(|(k, v)| (normalize_key(&k), v))
+        .collect();
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    for (name, surfaces) in names.iter().zip(&surfaces) {
+        for surface in surfaces.iter().filter(|s| *s != name) {
+            aliases.insert(surface.clone(), name.clone());
+        }
+    }
+
+    let mut index: HashMap<(String, String, String), usize> = HashMap::new();
+    let mut result: Vec<(String, String, String, usize, f64)> = Vec::new();
+    for ((h, r, t), confidence) in ids.into_iter().zip(confidences) {
+        let relation = relation_map.get(&normalize_key(&r)).cloned().unwrap_or(r);
+        let triple = (names[h].clone(), relation, names[t].clone());
+        match index.get(&triple) {
+            Some(&i) => {
+                result[i].3 += 1;
+                result[i].4 = aggregate_confidence(result[i].4, confidence, aggregate);
+            }
+            None => {
+                index.insert(triple.clone(), result.len());
+                result.push((triple.0, triple.1, triple.2, 1, confidence));
+            }
+        }
+    }
+
+    (result, aliases)
+}
+
+// 含省略号的同义词作为通配模式, 如 "由…组成" 匹配 "由若干部分组成"
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('…');
+    let Some(mut rest) = text.strip_prefix(parts.next().unwrap_or("")) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, canonical_map, default=None))]
+pub fn normalize_relations(
+    triples: Vec<(String, String, String)>,
+    canonical_map: HashMap<String, Vec<String>>,
+    default: Option<String>,
+) -> PyResult<(Vec<(String, String, String)>, Vec<(String, usize)>)> {
+    let mut exact: HashMap<String, String> = HashMap::new();
+    let mut patterns: Vec<(String, String)> = Vec::new();
+    for (canonical, synonyms) in &canonical_map {
+        exact.insert(normalize_key(canonical), canonical.clone());
+        for synonym in synonyms {
+            let key = normalize_key(synonym).replace("...", "…");
+            if key.contains('…') {
+                patterns.push((key, canonical.clone()));
+            } else {
+                exact.insert(key, canonical.clone());
+            }
+        }
+    }
+    // 较长的模式更具体, 优先匹配
+    patterns.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+    let mut result = Vec::with_capacity(triples.len());
+    let mut unmapped: HashMap<String, usize> = HashMap::new();
+    for (head, relation, tail) in triples {
+        let key = normalize_key(&relation);
+        let mapped = exact.get(&key).or_else(|| {
+            patterns
+                .iter()
+                .find(|(p, _)| wildcard_match(p, &key))
+                .map(|(_, c)| c)
+        });
+        let relation = match mapped {
+            Some(canonical) => canonical.clone(),
+            None => {
+                *unmapped.entry(relation.trim().to_string()).or_default() += 1;
+                match &default {
+                    Some(default) => default.clone(),
+                    None => continue,
+                }
+            }
+        };
+        result.push((head, relation, tail));
+    }
+    let mut unmapped: Vec<(String, usize)> = unmapped.into_iter().collect();
+    unmapped.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok((result, unmapped))
+}
+
+#[pyfunction]
+#[pyo3(signature = (old_triples, new_triples, rename_threshold=0.85))]
+pub fn diff_graphs(
+    old_triples: Vec<(String, String, String)>,
+    new_triples: Vec<(String, String, String)>,
+    rename_threshold: f64,
+) -> PyResult<(
+    Vec<(String, String, String)>,
+    Vec<(String, String, String)>,
+    Vec<((String, String, String), (String, String, String))>,
+    Vec<(String, String)>,
+)> {
+    let key = |(h, r, t): &(String, String, String)| {
+        (normalize_key(h), normalize_key(r), normalize_key(t))
+    };
+    let old_keys: Vec<_> = old_triples.iter().map(key).collect();
+    let new_keys: Vec<_> = new_triples.iter().map(key).collect();
+
+    // 先剔除规范形式完全相同的三元组
+    let old_set: HashSet<_> = old_keys.iter().collect();
+    let new_set: HashSet<_> = new_keys.iter().collect();
+    let old_rest: Vec<usize> = (0..old_keys.len())
+        .filter(|&i| !new_set.contains(&old_keys[i]))
+        .collect();
+    let mut new_rest: Vec<usize> = (0..new_keys.len())
+        .filter(|&j| !old_set.contains(&new_keys[j]))
+        .collect();
+
+    // 仅在一侧出现的实体之间按相似度贪心配对, 视为改名
+    let entities = |keys: &[(String, String, String)]| -> Vec<String> {
+        let mut seen = HashSet::new();
+        keys.iter()
+            .flat_map(|(h, _, t)| [h, t])
+            .filter(|e| seen.insert(e.as_str()))
+            .cloned()
+            .collect()
+    };
+    let old_entities = entities(&old_keys);
+    let new_entities = entities(&new_keys);
+    let old_lookup: HashSet<&String> = old_entities.iter().collect();
+    let new_lookup: HashSet<&String> = new_entities.iter().collect();
+    let old_only: Vec<&String> = old_entities
+        .iter()
+        .filter(|e| !new_lookup.contains(e))
+        .collect();
+    let new_only: Vec<Vec<char>> = new_entities
+        .iter()
+        .filter(|e| !old_lookup.contains(e))
+        .map(|e| e.chars().collect())
+        .collect();
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (i, a) in old_only.iter().enumerate() {
+        let a: Vec<char> = a.chars().collect();
+        for (j, b) in new_only.iter().enumerate() {
+            let score = normalized_similarity(&a, b, rename_threshold);
+            if score >= rename_threshold {
+                candidates.push((score, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|x, y| y.0.total_cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+    let mut rename: HashMap<&str, String> = HashMap::new();
+    let mut taken = vec![false; new_only.len()];
+    for (_, i, j) in candidates {
+        if !taken[j] && !rename.contains_key(old_only[i].as_str()) {
+            taken[j] = true;
+            rename.insert(old_only[i], new_only[j].iter().collect());
+        }
+    }
+
+    // 改名后相同的三元组, 以及同一实体对上关系变化的三元组, 记为变更
+    let renamed = |e: &String| rename.get(e.as_str()).cloned().unwrap_or_else(|| e.clone());
+    let mut changed = Vec::new();
+    let mut removed = Vec::new();
+    for i in old_rest {
+        let (h, r, t) = &old_keys[i];
+        let (h, t) = (renamed(h), renamed(t));
+        let same = new_rest
+            .iter()
+            .position(|&j| new_keys[j] == (h.clone(), r.clone(), t.clone()))
+            .or_else(|| {
+                new_rest
+                    .iter()
+                    .position(|&j| new_keys[j].0 == h && new_keys[j].2 == t)
+            });
+        match same {
+            Some(p) => {
+                let j = new_rest.remove(p);
+                changed.push((old_triples[i].clone(), new_triples[j].clone()));
+            }
+            None => removed.push(old_triples[i].clone()),
+        }
+    }
+    let added = new_rest
+        .into_iter()
+        .map(|j| new_triples[j].clone())
+        .collect();
+
+    // 改名以原始写法输出
+    let surface =
+        |keys: &[(String, String, String)], triples: &[(String, String, String)], name: &str| {
+            keys.iter()
+                .zip(triples)
+                .find_map(|(k, t)| {
+                    if k.0 == name {
+                        Some(t.0.clone())
+                    } else if k.2 == name {
+                        Some(t.2.clone())
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or_else(|| name.to_string())
+        };
+    let renames = old_only
+        .iter()
+        .filter_map(|e| {
+            rename.get(e.as_str()).map(|n| {
+                (
+                    surface(&old_keys, &old_triples, e),
+                    surface(&new_keys, &new_triples, n),
+                )
+            })
+        })
+        .collect();
+    Ok((added, removed, changed, renames))
+}
+
+#[pyfunction]
+#[pyo3(signature = (base, incoming, policy="keep_both"))]
+pub fn merge_graphs(
+    base: Vec<(String, String, String, f64)>,
+    incoming: Vec<(String, String, String, f64)>,
+    policy: &str,
+) -> PyResult<Vec<(String, String, String, f64)>> {
+    if !matches!(policy, "keep_both" | "prefer_confidence" | "prefer_newer") {
+        return Err(PyValueError::new_err(format!(
+            "未知的冲突策略: {policy}, 可选 keep_both / prefer_confidence / prefer_newer"
+        )));
+    }
+
+    // 按实体对分组, 组内相同关系只保留置信度最高的一条
+    let mut pair_index: HashMap<(String, String), usize> = HashMap::new();
+    let mut groups: Vec<Vec<(String, (String, String, String, f64), bool)>> = Vec::new();
+    let sources = base.into_iter().map(|t| (t, false));
+    for (triple, newer) in sources.chain(incoming.into_iter().map(|t| (t, true))) {
+        let pair = (normalize_key(&triple.0), normalize_key(&triple.2));
+        let i = *pair_index.entry(pair).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        let relation = normalize_key(&triple.1);
+        match groups[i].iter_mut().find(|(r, _, _)| *r == relation) {
+            Some((_, kept, kept_newer)) => {
+                *kept_newer |= newer;
+                kept.3 = kept.3.max(triple.3);
+            }
+            None => groups[i].push((relation, triple, newer)),
+        }
+    }
+
+    let mut result = Vec::new();
+    for group in groups {
+        let winner = match policy {
+            // 同置信度时取后加入的一条
+            "prefer_confidence" => group
+                .iter()
+                .enumerate()
+                .max_by(|(i, a), (j, b)| a.1 .3.total_cmp(&b.1 .3).then(i.cmp(j)))
+                .map(|(i, _)| i),
+            "prefer_newer" if group.iter().any(|(_, _, newer)| *newer) => {
+                // 来自新图的关系全部保留, 旧图中冲突的关系被替换
+                result.extend(
+                    group
+                        .into_iter()
+                        .filter(|(_, _, newer)| *newer)
+                        .map(|(_, t, _)| t),
+                );
+                continue;
+            }
+            _ => None,
+        };
+        match winner {
+            Some(i) => result.push(group.into_iter().nth(i).unwrap().1),
+            None => result.extend(group.into_iter().map(|(_, t, _)| t)),
+        }
+    }
+    Ok(result)
+}
+
+#[pyfunction]
+pub fn canonicalize_inverse(
+    triples: Vec<(String, String, String)>,
+    inverse_map: HashMap<String, String>,
+) -> PyResult<Vec<(String, String, String)>> {
+    let inverse: HashMap<String, String> = inverse_map
+        .into_iter()
+        .map(|(k, v)| (normalize_key(&k), v))
+        .collect();
+    // 互为逆关系的两项同时出现时无法确定规范方向
+    for (relation, canonical) in &inverse {
+        if inverse.contains_key(&normalize_key(canonical)) {
+            return Err(PyValueError::new_err(format!(
+                "逆关系映射存在循环: {relation} -> {canonical}"
+            )));
+        }
+    }
+
+    let mut seen: HashSet<(String, String, String)> = HashSet::new();
+    let mut result = Vec::with_capacity(triples.len());
+    for (head, relation, tail) in triples {
+        let triple = match inverse.get(&normalize_key(&relation)) {
+            Some(canonical) => (tail, canonical.clone(), head),
+            None => (head, relation, tail),
+        };
+        let key = (
+            normalize_key(&triple.0),
+            normalize_key(&triple.1),
+            normalize_key(&triple.2),
+        );
+        if seen.insert(key) {
+            result.push(triple);
+        }
+    }
+    Ok(result)
+}
+
+#[pyfunction]
+#[pyo3(signature = (triples, schema, entity_types=None))]
+pub fn validate_triples(
+    triples: Vec<(String, String, String)>,
+    schema: HashMap<String, HashMap<String, Vec<String>>>,
+    entity_types: Option<HashMap<String, String>>,
+) -> PyResult<Vec<(usize, String)>> {
+    for (relation, constraint) in &schema {
+        if let Some(key) = constraint
+            .keys()
+            .find(|k| !matches!(k.as_str(), "head" | "tail"))
+        {
+            return Err(PyValueError::new_err(format!(
+                "关系 {relation} 的约束键不合法: {key}, 可选 head / tail"
+            )));
+        }
+    }
+    let schema: HashMap<String, &HashMap<String, Vec<String>>> =
+        schema.iter().map(|(k, v)| (normalize_key(k), v)).collect();
+    let entity_types: HashMap<String, String> = entity_types
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (normalize_key(&k), v))
+        .collect();
+
+    // 检查实体类型是否在允许范围内, 未声明约束时不检查
+    let check =
+        |role: &str, label: &str, entity: &str, constraint: &HashMap<String, Vec<String>>| {
+            let allowed = constraint.get(role)?;
+            match entity_types.get(&normalize_key(entity)) {
+                Some(ty) if allowed.contains(ty) => None,
+                Some(ty) => Some(format!(
+                    "{label} {entity} 的类型 {ty} 不在允许范围内: {}",
+                    allowed.join(", ")
+                )),
+                None => Some(format!("{label} {entity} 缺少类型")),
+            }
+        };
+
+    let mut violations = Vec::new();
+    for (i, (head, relation, tail)) in triples.iter().enumerate() {
+        if head.trim().is_empty() || relation.trim().is_empty() || tail.trim().is_empty() {
+            violations.push((i, "头实体、关系或尾实体为空".to_string()));
+            continue;
+        }
+        let Some(constraint) = schema.get(&normalize_key(relation)) else {
+            violations.push((i, format!("未定义的关系: {}", relation.trim())));
+            continue;
+        };
+        violations.extend(check("head", "头实体", head.trim(), constraint).map(|r| (i, r)));
+        violations.extend(check("tail", "尾实体", tail.trim(), constraint).map(|r| (i, r)));
+    }
+    Ok(violations)
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(dedup_triples, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_triples, m)?)?;
+    m.add_function(wrap_pyfunction!(normalize_relations, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_graphs, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_graphs, m)?)?;
+    m.add_function(wrap_pyfunction!(canonicalize_inverse, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_triples, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use numpy::{PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use super::threads::install;
+
+// 按 8 路展开累加, 便于编译器自动向量化
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0f32; 8];
+    let (a_chunks, b_chunks) = (a.chunks_exact(8), b.chunks_exact(8));
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| x * y)
+        .sum();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for i in 0..8 {
+            lanes[i] += x[i] * y[i];
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+// 按得分降序取前 k 项, 同分时下标小者在前
+pub fn top_k(scores: Vec<f32>, k: usize) -> Vec<(usize, f32)> {
+    let mut indexed: Vec<(usize, f32)> = scores.into_iter().enumerate().collect();
+    let order = |a: &(usize, f32), b: &(usize, f32)| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0));
+    if k < indexed.len() {
+        if k == 0 {
+            return Vec::new();
+        }
+        indexed.select_nth_unstable_by(k - 1, order);
+        indexed.truncate(k);
+    }
+    indexed.sort_by(order);
+    indexed
+}
+
+#[pyfunction]
+#[pyo3(signature = (query, matrix, k=10))]
+pub fn topk_cosine(
+    py: Python<'_>,
+    query: PyReadonlyArray1<'_, f32>,
+    matrix: PyReadonlyArray2<'_, f32>,
+    k: usize,
+) -> PyResult<Vec<(usize, f32)>> {
+    let query = query.as_array().to_vec();
+    let matrix = matrix.as_array();
+    let (rows, dim) = matrix.dim();
+    if query.len() != dim {
+        return Err(PyValueError::new_err(format!(
+            "查询向量维度 {} 与矩阵列数 {} 不一致",
+            query.len(),
+            dim
+        )));
+    }
+    // 非连续数组先复制一份
+    let owned: Vec<f32>;
+    let data = match matrix.as_slice() {
+        Some(data) => data,
+        None => {
+            owned = matrix.iter().copied().collect();
+            &owned
+        }
+    };
+
+    let query_norm = dot(&query, &query).sqrt();
+    let scores: Vec<f32> = if rows == 0 || dim == 0 {
+        vec![0.0; rows]
+    } else {
+        py.allow_threads(|| {
+            install(|| {
+                data.par_chunks(dim)
+                    .map(|row| {
+                        let norm = dot(row, row).sqrt() * query_norm;
+                        if norm == 0.0 {
+                            0.0
+                        } else {
+                            dot(row, &query) / norm
+                        }
+                    })
+                    .collect()
+            })
+        })
+    };
+
+    Ok(top_k(scores, k))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(topk_cosine, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::data_type::{ByteArray, ByteArrayType};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use serde_json::{Map, Value};
+
+use super::json::py_to_json;
+
+fn io_error(path: &str, e: std::io::Error) -> PyErr {
+    PyValueError::new_err(format!("无法写入 {}: {}", path, e))
+}
+
+// 依次取出文本块与对应的元数据交给 f, 不在内存中保留已处理的行
+fn for_each_row(
+    chunks: &Bound<'_, PyAny>,
+    metadata: Option<&Bound<'_, PyAny>>,
+    mut f: impl FnMut(String, Option<Map<String, Value>>) -> PyResult<()>,
+) -> PyResult<usize> {
+    let mut metadata = metadata.map(|m| m.iter()).transpose()?;
+    let mut count = 0;
+    for chunk in chunks.iter()? {
+        let chunk = chunk?;
+        let text: &Bound<'_, PyString> = chunk.downcast()?;
+        let mut fields = None;
+        if let Some(items) = metadata.as_mut() {
+            let item = items.next().transpose()?.ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "metadata 数量少于 chunks, 第 {} 块缺少元数据",
+                    count
+                ))
+            })?;
+            match py_to_json(&item)? {
+                Value::Object(mut map) => {
+                    map.shift_remove("text");
+                    fields = Some(map);
+                }
+                Value::Null => {}
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "第 {} 块的元数据应为 dict 或 None",
+                        count
+                    )))
+                }
+            }
+        }
+        f(text.to_cow()?.into_owned(), fields)?;
+        count += 1;
+    }
+    if let Some(mut items) = metadata {
+        if items.next().is_some() {
+            return Err(PyValueError::new_err("metadata 数量多于 chunks"));
+        }
+    }
+    Ok(count)
+}
+
+// 每行为 {"text": 文本, **元数据}
+fn write_jsonl<W: Write>(
+    writer: &mut W,
+    path: &str,
+    chunks: &Bound<'_, PyAny>,
+    metadata: Option<&Bound<'_, PyAny>>,
+) -> PyResult<usize> {
+    for_each_row(chunks, metadata, |text, fields| {
+        let mut row = Map::new();
+        row.insert("text".to_string(), Value::String(text));
+        row.extend(fields.into_iter().flatten());
+        serde_json::to_writer(&mut *writer, &Value::Object(row))
+            .map_err(|e| io_error(path, e.into()))?;
+        writer.write_all(b"\n").map_err(|e| io_error(path, e))
+    })
+}
+
+// 每个行组的行数, 写出后释放, 内存占用与总行数无关
+const ROW_GROUP_SIZE: usize = 8192;
+
+const PARQUET_SCHEMA: &str = "
+message chunk {
+    required binary text (UTF8);
+    optional binary metadata (UTF8);
+}";
+
+fn parquet_error(path: &str, e: ParquetError) -> PyErr {
+    PyValueError::new_err(format!("无法写入 {}: {}", path, e))
+}
+
+fn write_row_group(
+    writer: &mut SerializedFileWriter<BufWriter<File>>,
+    texts: &[ByteArray],
+    metadata: &[ByteArray],
+    levels: &[i16],
+) -> Result<(), ParquetError> {
+    let mut row_group = writer.next_row_group()?;
+    for (values, levels) in [(texts, None), (metadata, Some(levels))] {
+        let mut column = row_group
+            .next_column()?
+            .ok_or_else(|| ParquetError::General("列数与 schema 不一致".to_string()))?;
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(values, levels, None)?;
+        column.close()?;
+    }
+    row_group.close()?;
+    Ok(())
+}
+
+// text 列为文本, metadata 列为元数据的 JSON 字符串, 无元数据时为 null
+fn write_parquet(
+    file: BufWriter<File>,
+    path: &str,
+    chunks: &Bound<'_, PyAny>,
+    metadata: Option<&Bound<'_, PyAny>>,
+    compression: Compression,
+) -> PyResult<usize> {
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA).unwrap());
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(compression)
+            .build(),
+    );
+    let mut writer =
+        SerializedFileWriter::new(file, schema, props).map_err(|e| parquet_error(path, e))?;
+    let mut texts = Vec::new();
+    let mut values = Vec::new();
+    let mut levels = Vec::new();
+    let count = for_each_row(chunks, metadata, |text, fields| {
+        texts.push(ByteArray::from(text.into_bytes()));
+        match fields {
+            Some(fields) => {
+                let json = serde_json::to_vec(&Value::Object(fields))
+                    .map_err(|e| io_error(path, e.into()))?;
+                values.push(ByteArray::from(json));
+                levels.push(1);
+            }
+            None => levels.push(0),
+        }
+        if texts.len() >= ROW_GROUP_SIZE {
+            write_row_group(&mut writer, &texts, &values, &levels)
+                .map_err(|e| parquet_error(path, e))?;
+            texts.clear();
+            values.clear();
+            levels.clear();
+        }
+        Ok(())
+    })?;
+    if !texts.is_empty() {
+        write_row_group(&mut writer, &texts, &values, &levels)
+            .map_err(|e| parquet_error(path, e))?;
+    }
+    writer
+        .into_inner()
+        .and_then(|mut file| file.flush().map_err(ParquetError::from))
+        .map_err(|e| parquet_error(path, e))?;
+    Ok(count)
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, chunks, metadata=None, format="jsonl", compression=None))]
+pub fn write_chunks(
+    path: &str,
+    chunks: &Bound<'_, PyAny>,
+    metadata: Option<&Bound<'_, PyAny>>,
+    format: &str,
+    compression: Option<&str>,
+) -> PyResult<usize> {
+    if !matches!(format, "jsonl" | "parquet") {
+        return Err(PyValueError::new_err(format!(
+            "未知的格式: {format}, 可选 jsonl / parquet"
+        )));
+    }
+    // 未指定时 Parquet 使用 zstd, JSONL 按扩展名判断
+    let compression = compression.unwrap_or(if format == "parquet" || path.ends_with(".zst") {
+        "zstd"
+    } else {
+        "none"
+    });
+    if !matches!(compression, "none" | "zstd") {
+        return Err(PyValueError::new_err(format!(
+            "未知的压缩方式: {compression}, 可选 none / zstd"
+        )));
+    }
+
+    let file = BufWriter::new(File::create(path).map_err(|e| io_error(path, e))?);
+    match (format, compression) {
+        ("parquet", "zstd") => write_parquet(
+            file,
+            path,
+            chunks,
+            metadata,
+            Compression::ZSTD(ZstdLevel::default()),
+        ),
+        ("parquet", _) => write_parquet(file, path, chunks, metadata, Compression::UNCOMPRESSED),
+        (_, "zstd") => {
+            let mut encoder = zstd::Encoder::new(file, 3).map_err(|e| io_error(path, e))?;
+            let count = write_jsonl(&mut encoder, path, chunks, metadata)?;
+            encoder
+                .finish()
+                .and_then(|mut file| file.flush())
+                .map_err(|e| io_error(path, e))?;
+            Ok(count)
+        }
+        _ => {
+            let mut file = file;
+            let count = write_jsonl(&mut file, path, chunks, metadata)?;
+            file.flush().map_err(|e| io_error(path, e))?;
+            Ok(count)
+        }
+    }
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(write_chunks, m)?)?;
+    Ok(())
+}
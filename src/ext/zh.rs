@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+
+const TS_CHARS: &str = include_str!("data/ts_chars.txt");
+
+// 一简对多繁时的简转繁取值, 映射到自身表示保持不变
+const ST_OVERRIDES: [(char, char); 24] = [
+    ('干', '幹'),
+    ('后', '後'),
+    ('台', '臺'),
+    ('里', '裏'),
+    ('系', '系'),
+    ('面', '面'),
+    ('只', '只'),
+    ('余', '餘'),
+    ('谷', '谷'),
+    ('表', '表'),
+    ('松', '松'),
+    ('征', '征'),
+    ('范', '范'),
+    ('胡', '胡'),
+    ('卷', '卷'),
+    ('向', '向'),
+    ('出', '出'),
+    ('致', '致'),
+    ('咸', '咸'),
+    ('周', '周'),
+    ('纤', '纖'),
+    ('杠', '杠'),
+    ('症', '症'),
+    ('辟', '辟'),
+];
+
+fn tables() -> &'static (HashMap<char, char>, HashMap<char, char>) {
+    static TABLES: OnceLock<(HashMap<char, char>, HashMap<char, char>)> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut t2s = HashMap::new();
+        let mut s2t = HashMap::new();
+        for line in TS_CHARS.lines().filter(|l| !l.starts_with('#')) {
+            for pair in line.split_whitespace() {
+                let mut chars = pair.chars();
+                if let (Some(t), Some(s)) = (chars.next(), chars.next()) {
+                    t2s.insert(t, s);
+                    s2t.entry(s).or_insert(t);
+                }
+            }
+        }
+        for (s, t) in ST_OVERRIDES {
+            if s == t {
+                s2t.remove(&s);
+            } else {
+                s2t.insert(s, t);
+            }
+        }
+        (t2s, s2t)
+    })
+}
+
+fn convert(text: &str, table: &HashMap<char, char>) -> String {
+    text.chars().map(|c| *table.get(&c).unwrap_or(&c)).collect()
+}
+
+#[pyfunction]
+pub fn t2s(text: &str) -> PyResult<String> {
+    Ok(convert(text, &tables().0))
+}
+
+#[pyfunction]
+pub fn s2t(text: &str) -> PyResult<String> {
+    Ok(convert(text, &tables().1))
+}
+
+pub fn warm_up() {
+    tables();
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(t2s, m)?)?;
+    m.add_function(wrap_pyfunction!(s2t, m)?)?;
+    Ok(())
+}
@@ -1,30 +1,53 @@
+// pyo3 0.22 的宏展开会触发 useless_conversion 误报
+#![allow(clippy::useless_conversion)]
+// 导出函数的返回值直接对应 Python 端的 tuple/list 结构
+#![allow(clippy::type_complexity)]
+
+use log::debug;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyString;
 use rand::Rng;
 
+mod ext;
+
+use ext::config::{split_sentences, CoreConfig};
+use ext::errors::{ChunkingError, LayoutError};
+use ext::length::{char_len, char_tail, grapheme_floor};
+
+type BBox = (f32, f32, f32, f32);
+
 #[pyfunction]
-pub fn get_list(text: &str) -> PyResult<Vec<String>> {
+pub fn get_list(py: Python<'_>, text: &str) -> PyResult<Vec<String>> {
     let mut list_string = String::new();
     let mut stack = 0;
-    let mut chars = text.chars();
-    let mut result = Vec::new();
+    // 最外层 '[' 的字符偏移
+    let mut start = 0;
 
-    while let Some(s) = chars.next() {
+    for (i, s) in text.chars().enumerate() {
         if s == '[' {
+            if stack == 0 {
+                start = i;
+            }
             stack += 1;
         }
         if stack > 0 {
             list_string.push(s);
         }
-        if s == ']' {
+        if s == ']' && stack > 0 {
             stack -= 1;
             if stack == 0 {
-                result = vec![list_string.to_string()];
-                break;
+                return Ok(vec![list_string]);
             }
         }
     }
 
-    Ok(result)
+    // 没有列表时返回空列表, 括号未闭合时报错
+    if stack > 0 {
+        return Err(ext::errors::parse_error(py, Some(start), "列表括号未闭合"));
+    }
+    debug!("文本中未找到列表, 返回空列表");
+    Ok(Vec::new())
 }
 
 #[pyfunction]
@@ -56,108 +79,218 @@ pub fn get_longest_seq(nums: Vec<i32>) -> PyResult<(i32, i32)> {
     Ok((max_start, max_end))
 }
 
+// n 为负数时转换为 usize 会溢出
+fn check_chunk_size(n: i32) -> PyResult<()> {
+    if n <= 0 {
+        return Err(ChunkingError::new_err(format!(
+            "分块长度必须大于 0, 实际为 {}",
+            n
+        )));
+    }
+    Ok(())
+}
+
+// abi3 (兼容 Python 3.9) 下无法直接借用 str 的 UTF-8 缓冲区, 逐个编码为 bytes 后借用,
+// 处理完即释放, 不会同时持有整本书的副本; 处理时释放 GIL
+fn for_each_str(
+    py: Python<'_>,
+    strings: &[Bound<'_, PyString>],
+    mut f: impl FnMut(&str) + Send,
+) -> PyResult<()> {
+    for string in strings {
+        let encoded = string.encode_utf8()?;
+        let text = std::str::from_utf8(encoded.as_bytes())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        py.allow_threads(|| f(text));
+    }
+    Ok(())
+}
+
+// 输入字符串逐个交给 f 处理的驱动函数, 由调用方决定字符串来源
+pub(crate) type StrSource<'a> = dyn FnOnce(&mut (dyn FnMut(&str) + Send)) -> PyResult<()> + 'a;
+
 #[pyfunction]
-pub fn optimize_length(s: Vec<String>, n: i32) -> PyResult<Vec<String>> {
+#[pyo3(signature = (s, n=None, config=None))]
+pub fn optimize_length(
+    py: Python<'_>,
+    s: Vec<Bound<'_, PyString>>,
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    optimize_length_with(n, config, Box::new(|f| for_each_str(py, &s, f)))
+}
+
+pub(crate) fn optimize_length_with(
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+    source: Box<StrSource<'_>>,
+) -> PyResult<Vec<String>> {
+    let config = config.unwrap_or_default();
+    let n = n.unwrap_or(config.chunk_size);
+    check_chunk_size(n)?;
+    let n = n as usize;
     let mut result: Vec<String> = Vec::new();
     let mut buffer = String::new();
-
-    for string in s {
-        if string.chars().count() < n as usize {
-            buffer.push_str(&string);
-            // buffer.push_str("\n");
-            if buffer.chars().count() >= n as usize {
-                let trimmed = buffer.trim_end().to_string();
-                result.push(trimmed);
+    let mut buffer_len = 0;
+    // 按分隔符切分的段落数与单独成块的句子数, 处理结束后汇总记录, 避免逐句日志
+    let mut split_paragraphs = 0;
+    let mut long_sentences = 0;
+
+    source(&mut |string: &str| {
+        let string_len = char_len(string);
+        if string_len < n {
+            buffer.push_str(string);
+            buffer_len += string_len;
+            if buffer_len >= n {
+                result.push(buffer.trim_end().to_string());
                 buffer.clear();
+                buffer_len = 0;
             }
         } else {
-            let sentences: Vec<&str> = string.split('。').collect();
-            for (i, sentence) in sentences.iter().enumerate() {
-                let mut current = String::from(*sentence);
-                if i < sentences.len() - 1 {
-                    current.push('。');
-                }
-                if current.chars().count() < n as usize {
-                    buffer.push_str(&current);
-                    if buffer.chars().count() >= n as usize {
+            let sentences = split_sentences(string, &config.delimiters, config.grapheme_safe);
+            split_paragraphs += 1;
+            for (sentence, delimiter) in sentences {
+                let current_len = char_len(sentence) + char_len(delimiter);
+                if current_len < n {
+                    buffer.push_str(sentence);
+                    buffer.push_str(delimiter);
+                    buffer_len += current_len;
+                    if buffer_len >= n {
                         result.push(buffer.clone());
                         buffer.clear();
+                        buffer_len = 0;
                     }
                 } else {
-                    result.push(current);
+                    long_sentences += 1;
+                    result.push(format!("{}{}", sentence, delimiter));
                 }
             }
         }
-    }
+    })?;
     if !buffer.is_empty() {
         result.push(buffer);
     }
+    debug!(
+        "{} 个段落长度不小于 {}, 按分隔符切分, 其中 {} 句单独成块",
+        split_paragraphs, n, long_sentences
+    );
 
     Ok(result)
 }
 
 #[pyfunction]
-pub fn merge(texts: Vec<String>, n: i32) -> PyResult<Vec<String>> {
-    let mut result = Vec::new();
-    let mut chunks = Vec::new();
-
-    // 分割并收集所有文本片段
-    for text in texts {
-        chunks.extend(
-            text.split('。')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty()),
-        );
-    }
-
-    // 为不以句号结尾的片段添加句号
-    chunks = chunks
-        .into_iter()
-        .map(|chunk| {
-            if !chunk.ends_with('。') {
-                chunk + "。"
-            } else {
-                chunk
-            }
-        })
-        .collect();
+#[pyo3(signature = (texts, n=None, config=None))]
+pub fn merge(
+    py: Python<'_>,
+    texts: Vec<Bound<'_, PyString>>,
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+) -> PyResult<Vec<String>> {
+    merge_with(n, config, Box::new(|f| for_each_str(py, &texts, f)))
+}
 
+pub(crate) fn merge_with(
+    n: Option<i32>,
+    config: Option<CoreConfig>,
+    source: Box<StrSource<'_>>,
+) -> PyResult<Vec<String>> {
+    let config = config.unwrap_or_default();
+    let n = n.unwrap_or(config.chunk_size);
+    check_chunk_size(n)?;
+    let n = n as usize;
+    if config.overlap >= n {
+        return Err(ChunkingError::new_err(format!(
+            "overlap ({}) 必须小于分块长度 ({})",
+            config.overlap, n
+        )));
+    }
+    let mut result = Vec::new();
+    // 末尾没有分隔符的片段补上首个分隔符
+    let fallback = config.delimiters.first().map_or("。", String::as_str);
     let mut current = String::new();
+    let mut current_len = 0;
     let mut is_first_exceed = true;
-
-    for chunk in chunks {
-        let current_len = current.chars().count();
-        let chunk_len = chunk.chars().count();
-
-        if current_len + chunk_len < n as usize {
-            current.push_str(&chunk);
-        } else if current_len + chunk_len == n as usize {
-            current.push_str(&chunk);
-            result.push(current);
-            current = String::new();
-            is_first_exceed = true;
-        } else {
-            if is_first_exceed {
+    // 恰好满块、允许超出与再次超出的次数, 处理结束后汇总记录
+    let (mut exact, mut exceeded, mut resplit) = (0, 0, 0);
+
+    // 逐个切分文本, 片段以切片形式直接拼接到当前块
+    source(&mut |text: &str| {
+        for (sentence, delimiter) in split_sentences(text, &config.delimiters, config.grapheme_safe)
+        {
+            let sentence = sentence.trim();
+            if sentence.is_empty() {
+                continue;
+            }
+            let delimiter = if delimiter.is_empty() {
+                fallback
+            } else {
+                delimiter
+            };
+            let chunk_len = char_len(sentence) + char_len(delimiter);
+
+            if current_len + chunk_len < n {
+                current.push_str(sentence);
+                current.push_str(delimiter);
+                current_len += chunk_len;
+            } else if current_len + chunk_len == n {
+                exact += 1;
+                current.push_str(sentence);
+                current.push_str(delimiter);
+                result.push(current.clone());
+                current.clear();
+                current_len = 0;
+                is_first_exceed = true;
+            } else if is_first_exceed {
+                exceeded += 1;
                 is_first_exceed = false;
-                current.push_str(&chunk);
+                current.push_str(sentence);
+                current.push_str(delimiter);
+                current_len += chunk_len;
             } else {
+                resplit += 1;
                 if !current.is_empty() {
-                    result.push(current);
+                    result.push(current.clone());
+                    current.clear();
                 }
-                current = chunk;
+                current.push_str(sentence);
+                current.push_str(delimiter);
+                current_len = chunk_len;
                 is_first_exceed = true;
             }
         }
-    }
+    })?;
 
     if !current.is_empty() {
         result.push(current);
     }
+    debug!(
+        "{} 块长度恰为 {}, {} 次允许超出, {} 次再次超出后切分",
+        exact, n, exceeded, resplit
+    );
+
+    // 每块之前拼接上一块末尾的 overlap 个字符
+    if config.overlap > 0 {
+        let tails: Vec<String> = result
+            .iter()
+            .map(|chunk| {
+                let tail = char_tail(chunk, config.overlap);
+                if config.grapheme_safe {
+                    // 向前扩展到字素簇边界, 不截断 emoji 与组合字符
+                    chunk[grapheme_floor(chunk, chunk.len() - tail.len())..].to_string()
+                } else {
+                    tail.to_string()
+                }
+            })
+            .collect();
+        for (chunk, tail) in result.iter_mut().skip(1).zip(tails) {
+            chunk.insert_str(0, &tail);
+        }
+    }
 
     Ok(result)
 }
 
-fn iou(box1: (f32, f32, f32, f32), box2: (f32, f32, f32, f32)) -> f32 {
+fn iou(box1: BBox, box2: BBox) -> f32 {
     let x1 = box1.0.max(box2.0);
     let y1 = box1.1.max(box2.1);
     let x2 = box1.2.min(box2.2);
@@ -177,61 +310,104 @@ fn iou(box1: (f32, f32, f32, f32), box2: (f32, f32, f32, f32)) -> f32 {
     }
 }
 
-fn contained(box1: (f32, f32, f32, f32), box2: (f32, f32, f32, f32)) -> bool {
+fn contained(box1: BBox, box2: BBox) -> bool {
     box1.0 <= box2.0 && box1.1 <= box2.1 && box1.2 >= box2.2 && box1.3 >= box2.3
 }
 
 #[pyfunction]
+#[pyo3(signature = (detections, iou_threshold=None, config=None, stable=None))]
 pub fn structure(
-    detections: Vec<(String, (f32, f32, f32, f32))>,
-    iou_threshold: f32,
-) -> PyResult<Vec<(String, (f32, f32, f32, f32))>> {
-    // 先转换为 mut
-    let mut detections = detections;
-    let mut filtered_detections = Vec::new();
-
-    while !detections.is_empty() {
-        let detection = detections.remove(0);
-        let mut keep = true;
-
-        // 用于存储待移除的检测框
-        let mut to_remove = Vec::new();
-
-        for other_detection in detections.clone() {
-            if iou(detection.1, other_detection.1) > iou_threshold {
-                // 随机选择是否移除
-                if rand::thread_rng().gen_bool(0.5) {
+    py: Python<'_>,
+    detections: Vec<(String, BBox)>,
+    iou_threshold: Option<f32>,
+    config: Option<CoreConfig>,
+    stable: Option<bool>,
+) -> PyResult<Vec<(String, BBox)>> {
+    let config = config.unwrap_or_default();
+    let iou_threshold = iou_threshold.unwrap_or(config.iou_threshold);
+    let stable = stable.unwrap_or(config.stable);
+    // 因 IoU 超过阈值与因包含关系移除的检测框数, 释放 GIL 期间不记录日志
+    let (mut by_iou, mut by_containment) = (0, 0);
+    let filtered = py.allow_threads(|| {
+        if !(0.0..=1.0).contains(&iou_threshold) {
+            return Err(LayoutError::new_err(format!(
+                "iou_threshold 应在 [0, 1] 内, 实际为 {}",
+                iou_threshold
+            )));
+        }
+        for (i, (label, (x1, y1, x2, y2))) in detections.iter().enumerate() {
+            let finite = [x1, y1, x2, y2].iter().all(|v| v.is_finite());
+            if !finite || x2 < x1 || y2 < y1 {
+                return Err(LayoutError::new_err(format!(
+                    "第 {} 个检测框 {} 不合法: ({}, {}, {}, {})",
+                    i, label, x1, y1, x2, y2
+                )));
+            }
+        }
+
+        // 先转换为 mut
+        let mut detections = detections;
+        let mut filtered_detections = Vec::new();
+
+        while !detections.is_empty() {
+            let detection = detections.remove(0);
+            let mut keep = true;
+
+            // 用于存储待移除的检测框
+            let mut to_remove = Vec::new();
+
+            for other_detection in detections.clone() {
+                let overlap = iou(detection.1, other_detection.1);
+                if overlap > iou_threshold {
+                    // 随机选择是否移除, stable 时总是保留靠前的检测框
+                    by_iou += 1;
+                    if stable || rand::thread_rng().gen_bool(0.5) {
+                        to_remove.push(other_detection);
+                    } else {
+                        keep = false;
+                        break;
+                    }
+                } else if contained(detection.1, other_detection.1) {
+                    by_containment += 1;
                     to_remove.push(other_detection);
-                } else {
+                } else if contained(other_detection.1, detection.1) {
+                    by_containment += 1;
                     keep = false;
                     break;
                 }
-            } else if contained(detection.1, other_detection.1) {
-                to_remove.push(other_detection);
-            } else if contained(other_detection.1, detection.1) {
-                keep = false;
-                break;
             }
-        }
 
-        for item in to_remove {
-            detections.retain(|x| x != &item);
-        }
+            for item in to_remove {
+                detections.retain(|x| x != &item);
+            }
 
-        if keep {
-            filtered_detections.push(detection);
+            if keep {
+                filtered_detections.push(detection);
+            }
         }
-    }
 
-    Ok(filtered_detections)
+        Ok(filtered_detections)
+    })?;
+    debug!(
+        "IoU 超过 {} 移除 {} 个检测框, 因包含关系移除 {} 个",
+        iou_threshold, by_iou, by_containment
+    );
+    Ok(filtered)
 }
 
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(get_list, m)?)?;
-    m.add_function(wrap_pyfunction!(structure, m)?)?;
+    let chunk = ext::submodule(m, "chunk")?;
+    chunk.add_function(wrap_pyfunction!(optimize_length, &chunk)?)?;
+    chunk.add_function(wrap_pyfunction!(merge, &chunk)?)?;
+    ext::arrow::register(&chunk)?;
+    ext::writer::register(&chunk)?;
+    ext::mmap::register_chunk(&chunk)?;
+    let layout = ext::submodule(m, "layout")?;
+    layout.add_function(wrap_pyfunction!(structure, &layout)?)?;
+    let parse = ext::submodule(m, "parse")?;
+    parse.add_function(wrap_pyfunction!(get_list, &parse)?)?;
     m.add_function(wrap_pyfunction!(get_longest_seq, m)?)?;
-    m.add_function(wrap_pyfunction!(optimize_length, m)?)?;
-    m.add_function(wrap_pyfunction!(merge, m)?)?;
+    ext::register(m)?;
     Ok(())
 }